@@ -0,0 +1,60 @@
+//! Benchmarks every registered solution on its bundled input, measuring the
+//! generator and the solver separately. Days without an `#[aoc_generator]`
+//! parse inside their solver, so their generator time is just copying the
+//! input.
+//!
+//! Run a subset with a filter, e.g. `cargo bench -- day12/`, and compare
+//! runs with criterion's baselines:
+//!
+//! ```text
+//! cargo bench -- --save-baseline before
+//! # ...make changes...
+//! cargo bench -- --baseline before
+//! ```
+
+use std::hint::black_box;
+
+use adventofcode_2022::registry::{self, Solution};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn bench_id(solution: &Solution) -> String {
+    match solution.name {
+        Some(name) => format!("part{}_{}", solution.part, name.to_lowercase()),
+        None => format!("part{}", solution.part),
+    }
+}
+
+fn solutions(c: &mut Criterion) {
+    for day in registry::days() {
+        let Ok(input) = std::fs::read_to_string(registry::input_path(day)) else {
+            continue;
+        };
+
+        let mut group = c.benchmark_group(format!("day{day}"));
+
+        // some solutions take around a second on the real input
+        group.sample_size(10);
+
+        for solution in registry::find(Some(day), None, None) {
+            let id = bench_id(solution);
+
+            group.bench_function(format!("{id}/generator"), |b| {
+                b.iter(|| solution.generate(black_box(&input)).unwrap())
+            });
+
+            let runner = solution.generate(&input).unwrap();
+            group.bench_function(format!("{id}/solver"), |b| {
+                b.iter(|| {
+                    runner.bench(|answer| {
+                        black_box(answer);
+                    })
+                })
+            });
+        }
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, solutions);
+criterion_main!(benches);
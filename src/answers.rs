@@ -0,0 +1,141 @@
+use std::error::Error;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::registry::{self, Solution};
+
+/// Expected answers for the bundled puzzle inputs, keyed by `[dayN]` tables
+/// holding `partM` values. Named variants of a part share its answer.
+pub struct Answers(toml::Table);
+
+/// What happened when a solution was checked against its expected answer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    Fail { expected: String, actual: String },
+    Unrecorded { actual: String },
+    NoInput,
+    Error(String),
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        Ok(std::fs::read_to_string(path)?.parse()?)
+    }
+
+    pub fn expected(&self, day: u32, part: u32) -> Option<String> {
+        match self
+            .0
+            .get(&format!("day{day}"))?
+            .get(format!("part{part}"))?
+        {
+            toml::Value::String(s) => Some(s.clone()),
+            toml::Value::Integer(n) => Some(n.to_string()),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Answers {
+    type Err = toml::de::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Self)
+    }
+}
+
+impl Outcome {
+    /// Whether the outcome should count as a regression. Days without a
+    /// bundled input can't be checked, so they aren't.
+    pub fn is_failure(&self) -> bool {
+        !matches!(self, Outcome::Pass | Outcome::NoInput)
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Pass => write!(f, "ok"),
+            Outcome::Fail { expected, actual } => {
+                write!(f, "FAILED, expected {expected:?} but got {actual:?}")
+            }
+            Outcome::Unrecorded { actual } => write!(f, "no recorded answer, got {actual:?}"),
+            Outcome::NoInput => write!(f, "skipped, no bundled input"),
+            Outcome::Error(e) => write!(f, "FAILED: {e}"),
+        }
+    }
+}
+
+/// Where the expected answers live, next to the bundled inputs.
+pub fn answers_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("input/2022/answers.toml")
+}
+
+/// Runs `solution` against its bundled input and compares the result with
+/// the expected answer.
+pub fn check(solution: &Solution, answers: &Answers) -> Outcome {
+    let input = match std::fs::read_to_string(registry::input_path(solution.day)) {
+        Ok(input) => input,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Outcome::NoInput,
+        Err(e) => return Outcome::Error(e.to_string()),
+    };
+
+    let actual = match solution.solve(&input) {
+        Ok(actual) => actual,
+        Err(e) => return Outcome::Error(e.to_string()),
+    };
+
+    match answers.expected(solution.day, solution.part) {
+        Some(expected) if expected == actual => Outcome::Pass,
+        Some(expected) => Outcome::Fail { expected, actual },
+        None => Outcome::Unrecorded { actual },
+    }
+}
+
+/// Checks every solution in `solutions` on its own thread, returning the
+/// outcomes in the same order.
+pub fn check_all(solutions: &[&'static Solution], answers: &Answers) -> Vec<Outcome> {
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = solutions
+            .iter()
+            .map(|solution| scope.spawn(|| check(solution, answers)))
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|h| {
+                h.join()
+                    .unwrap_or_else(|_| Outcome::Error("panicked".to_string()))
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expected() {
+        let answers: Answers = "[day1]\npart1 = 42\npart2 = \"abc\"".parse().unwrap();
+        assert_eq!(answers.expected(1, 1), Some("42".to_string()));
+        assert_eq!(answers.expected(1, 2), Some("abc".to_string()));
+        assert_eq!(answers.expected(2, 1), None);
+    }
+
+    #[test]
+    fn bundled_inputs() {
+        let answers = Answers::load(&answers_path()).unwrap();
+        let solutions: Vec<_> = registry::SOLUTIONS.iter().collect();
+
+        let failures: Vec<_> = solutions
+            .iter()
+            .zip(check_all(&solutions, &answers))
+            .filter(|(_, outcome)| outcome.is_failure())
+            .map(|(solution, outcome)| format!("{}: {outcome}", solution.label()))
+            .collect();
+
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+}
@@ -0,0 +1,256 @@
+use std::error::Error;
+use std::io::Read;
+use std::process::ExitCode;
+use std::time::Instant;
+
+use adventofcode_2022::answers::{self, Answers};
+use adventofcode_2022::error::ParseError;
+use adventofcode_2022::registry::{self, Solution};
+
+const USAGE: &str = "usage: aoc2022 list
+       aoc2022 <dayN|all> [partM] [--name NAME] [--input FILE|-] [--header LINE]...
+       aoc2022 check <dayN|all> [partM] [--name NAME]
+
+Runs the matching solutions against input/2022/dayN.txt, skipping days
+without one, or against FILE (or stdin for '-') when --input is given. Each
+--header LINE is added before the input, for days that read settings from a
+header (e.g. day 15's 'row y=10'). 'check' instead compares their answers on
+the bundled inputs with input/2022/answers.toml.";
+
+struct Args {
+    day: Option<u32>,
+    part: Option<u32>,
+    name: Option<String>,
+    input: Option<String>,
+    header: Vec<String>,
+}
+
+fn parse_number(arg: &str, prefix: &str) -> Result<u32, String> {
+    arg.strip_prefix(prefix)
+        .and_then(|n| n.parse().ok())
+        .ok_or_else(|| format!("expected '{prefix}N', found '{arg}'"))
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let day = match args.next().as_deref() {
+        Some("all") => None,
+        Some(arg) => Some(parse_number(arg, "day")?),
+        None => return Err("missing day".to_string()),
+    };
+
+    let mut parsed = Args {
+        day,
+        part: None,
+        name: None,
+        input: None,
+        header: Vec::new(),
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--name" => parsed.name = Some(args.next().ok_or("--name needs a value")?),
+            "--input" => parsed.input = Some(args.next().ok_or("--input needs a value")?),
+            "--header" => parsed
+                .header
+                .push(args.next().ok_or("--header needs a value")?),
+            part if parsed.part.is_none() => parsed.part = Some(parse_number(part, "part")?),
+            other => return Err(format!("unexpected argument '{other}'")),
+        }
+    }
+
+    if parsed.day.is_none() && parsed.input.is_some() {
+        return Err("--input needs a specific day".to_string());
+    }
+
+    if parsed.day.is_none() && !parsed.header.is_empty() {
+        return Err("--header needs a specific day".to_string());
+    }
+
+    Ok(parsed)
+}
+
+/// Reads the input from `input`, or the bundled input for `day` when it's
+/// not given. `None` if there's no bundled input for that day.
+fn read_input(day: u32, input: Option<&str>) -> Result<Option<String>, String> {
+    match input {
+        Some("-") => {
+            let mut buf = String::new();
+            std::io::stdin()
+                .read_to_string(&mut buf)
+                .map_err(|e| format!("failed to read stdin: {e}"))?;
+            Ok(Some(buf))
+        }
+        Some(path) => std::fs::read_to_string(path)
+            .map(Some)
+            .map_err(|e| format!("failed to read {path}: {e}")),
+        None => {
+            let path = registry::input_path(day);
+            match std::fs::read_to_string(&path) {
+                Ok(input) => Ok(Some(input)),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(format!("failed to read {}: {e}", path.display())),
+            }
+        }
+    }
+}
+
+/// Describes an error from generating the input, counting lines from the
+/// start of the input itself rather than the `header_lines` added before it.
+fn generate_error(e: &(dyn Error + 'static), header_lines: usize) -> String {
+    match e.downcast_ref::<ParseError>() {
+        // "line N, ..." becomes "--header N, ..."
+        Some(e) if e.line <= header_lines => e.to_string().replacen("line", "--header", 1),
+        Some(e) => ParseError {
+            line: e.line - header_lines,
+            ..e.clone()
+        }
+        .to_string(),
+        None => e.to_string(),
+    }
+}
+
+/// Runs one solution, printing its answer and timings. Returns whether it
+/// succeeded.
+fn run(solution: &Solution, input: &str, header_lines: usize) -> bool {
+    let label = solution.label();
+    let start = Instant::now();
+
+    let runner = match solution.generate(input) {
+        Ok(runner) => runner,
+        Err(e) => {
+            let e = generate_error(e.as_ref(), header_lines);
+            eprintln!("{label}: FAILED while generating: {e}");
+            return false;
+        }
+    };
+
+    let generated = Instant::now();
+
+    match runner.try_run() {
+        Ok(answer) => {
+            let solved = Instant::now();
+            let answer = answer.to_string();
+
+            // multi-line answers (e.g. rendered letters) start on their own line
+            if answer.contains('\n') {
+                println!("{label}:\n{answer}");
+            } else {
+                println!("{label}: {answer}");
+            }
+
+            println!(
+                "\tgenerator: {:?}, runner: {:?}",
+                generated - start,
+                solved - generated
+            );
+            true
+        }
+        Err(e) => {
+            eprintln!("{label}: FAILED while running: {e}");
+            false
+        }
+    }
+}
+
+/// Checks the solutions against the expected answers, printing one line per
+/// solution. Returns whether none of them regressed.
+fn check(solutions: &[&'static Solution]) -> bool {
+    let path = answers::answers_path();
+    let answers = match Answers::load(&path) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("failed to load {}: {e}", path.display());
+            return false;
+        }
+    };
+
+    let outcomes = answers::check_all(solutions, &answers);
+
+    for (solution, outcome) in solutions.iter().zip(&outcomes) {
+        println!("{}: {outcome}", solution.label());
+    }
+
+    !outcomes.iter().any(|o| o.is_failure())
+}
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1).peekable();
+
+    match args.peek().map(String::as_str) {
+        None | Some("-h" | "--help") => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Some("list") => {
+            for solution in registry::SOLUTIONS {
+                println!("{}", solution.label());
+            }
+            return ExitCode::SUCCESS;
+        }
+        _ => {}
+    }
+
+    let checking = args.next_if_eq("check").is_some();
+
+    let args = match parse_args(args) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{e}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let solutions: Vec<_> = registry::find(args.day, args.part, args.name.as_deref()).collect();
+
+    if solutions.is_empty() {
+        eprintln!("no matching solutions, see 'aoc2022 list'");
+        return ExitCode::FAILURE;
+    }
+
+    if checking {
+        if args.input.is_some() || !args.header.is_empty() {
+            eprintln!("'check' always uses the bundled inputs\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+
+        return if check(&solutions) {
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::FAILURE
+        };
+    }
+
+    let header: String = args.header.iter().map(|line| format!("{line}\n")).collect();
+    let mut ok = true;
+
+    for day in registry::days() {
+        let day_solutions: Vec<_> = solutions.iter().filter(|s| s.day == day).collect();
+        if day_solutions.is_empty() {
+            continue;
+        }
+
+        let input = match read_input(day, args.input.as_deref()) {
+            Ok(Some(input)) => format!("{header}{input}"),
+            // like 'check', days without a bundled input aren't failures
+            Ok(None) => {
+                eprintln!("Day {day}: skipped, no bundled input");
+                continue;
+            }
+            Err(e) => {
+                eprintln!("Day {day}: {e}");
+                ok = false;
+                continue;
+            }
+        };
+
+        for solution in day_solutions {
+            ok &= run(solution, &input, args.header.len());
+        }
+    }
+
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -1,21 +1,39 @@
 use std::collections::BinaryHeap;
+use std::fmt;
+
+use crate::error::ParseError;
+
+// part 2 adds up the three elves carrying the most
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TooFewElves(pub usize);
+
+impl fmt::Display for TooFewElves {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "only {} elves, but the top three are needed", self.0)
+    }
+}
+
+impl std::error::Error for TooFewElves {}
 
 #[aoc_generator(day1)]
-pub fn input_generator(input: &str) -> Vec<Vec<u32>> {
+pub fn input_generator(input: &str) -> Result<Vec<Vec<u32>>, ParseError> {
     let mut all = Vec::new();
     let mut cur = Vec::new();
 
-    for line in input.lines() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             all.push(cur);
             cur = Vec::new();
         } else {
-            cur.push(line.parse().unwrap());
+            let calories = line
+                .parse()
+                .map_err(|_| ParseError::new(i + 1, 1, line, "calorie count"))?;
+            cur.push(calories);
         }
     }
 
     all.push(cur);
-    all
+    Ok(all)
 }
 
 #[aoc(day1, part1)]
@@ -24,14 +42,18 @@ pub fn solve_part1(input: &[Vec<u32>]) -> u32 {
 }
 
 #[aoc(day1, part2)]
-pub fn solve_part2(input: &[Vec<u32>]) -> u32 {
+pub fn solve_part2(input: &[Vec<u32>]) -> Result<u32, TooFewElves> {
     let mut heap: BinaryHeap<u32> = BinaryHeap::new();
 
     for elf in input.iter() {
         heap.push(elf.iter().sum());
     }
 
-    heap.pop().unwrap() + heap.pop().unwrap() + heap.pop().unwrap()
+    if heap.len() < 3 {
+        return Err(TooFewElves(heap.len()));
+    }
+
+    Ok(heap.pop().unwrap() + heap.pop().unwrap() + heap.pop().unwrap())
 }
 
 #[cfg(test)]
@@ -41,8 +63,9 @@ mod tests {
     #[test]
     fn examples_part1() {
         assert_eq!(
-            solve_part1(&input_generator(
-                "1000\n\
+            solve_part1(
+                &input_generator(
+                    "1000\n\
 		 2000\n\
 		 3000\n\
 		 \n\
@@ -56,7 +79,9 @@ mod tests {
 		 9000\n\
 		 \n\
 		 10000"
-            )),
+                )
+                .unwrap()
+            ),
             24000
         );
     }
@@ -64,8 +89,9 @@ mod tests {
     #[test]
     fn examples_part2() {
         assert_eq!(
-            solve_part2(&input_generator(
-                "1000\n\
+            solve_part2(
+                &input_generator(
+                    "1000\n\
 		 2000\n\
 		 3000\n\
 		 \n\
@@ -79,8 +105,25 @@ mod tests {
 		 9000\n\
 		 \n\
 		 10000"
-            )),
-            45000
+                )
+                .unwrap()
+            ),
+            Ok(45000)
+        );
+    }
+
+    #[test]
+    fn too_few_elves() {
+        let input = input_generator("1000\n\n2000").unwrap();
+        assert_eq!(solve_part1(&input), 2000);
+        assert_eq!(solve_part2(&input), Err(TooFewElves(2)));
+    }
+
+    #[test]
+    fn invalid_calories() {
+        assert_eq!(
+            input_generator("1000\n20x0").err(),
+            Some(ParseError::new(2, 1, "20x0", "calorie count"))
         );
     }
 }
@@ -1,19 +1,24 @@
+use crate::error::ParseError;
+
 pub enum Instruction {
     Nop,
     Addx(i64),
 }
 
-impl From<&str> for Instruction {
-    fn from(s: &str) -> Self {
+impl TryFrom<&str> for Instruction {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
         if s == "noop" {
-            return Self::Nop;
+            return Ok(Self::Nop);
         }
 
-        let mut split = s.split(' ');
-
-        match (split.next().unwrap(), split.next().unwrap()) {
-            ("addx", n) => Self::Addx(n.parse().unwrap()),
-            (other, _) => panic!("Unknown instruction {}", other),
+        match s.split_once(' ') {
+            Some(("addx", n)) => n
+                .parse()
+                .map(Self::Addx)
+                .map_err(|_| ParseError::at(s, n, "addx operand")),
+            _ => Err(ParseError::at(s, s, "'noop' or 'addx'")),
         }
     }
 }
@@ -71,8 +76,12 @@ impl<'a> Iterator for Cpu<'a> {
 }
 
 #[aoc_generator(day10)]
-pub fn input_generator(input: &str) -> Vec<Instruction> {
-    input.lines().map(Instruction::from).collect()
+pub fn input_generator(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, l)| Instruction::try_from(l).map_err(|e| e.shift_lines(i)))
+        .collect()
 }
 
 #[aoc(day10, part1)]
@@ -261,7 +270,7 @@ noop";
 
     #[test]
     fn examples_part1() {
-        let short_example = input_generator("noop\naddx 3\naddx -5");
+        let short_example = input_generator("noop\naddx 3\naddx -5").unwrap();
         let mut cpu = Cpu::new(&short_example);
         assert_eq!(cpu.next().unwrap(), 1);
         assert_eq!(cpu.next().unwrap(), 1);
@@ -269,7 +278,7 @@ noop";
         assert_eq!(cpu.next().unwrap(), 4);
         assert_eq!(cpu.next().unwrap(), 4);
 
-        let example = input_generator(EXAMPLE_INPUT);
+        let example = input_generator(EXAMPLE_INPUT).unwrap();
         let cpu = Cpu::new(&example);
 
         // cpu.enumerate()
@@ -293,7 +302,7 @@ noop";
         let mut cpu = cpu.skip(39);
         assert_eq!(cpu.next().unwrap() * 220, 3960);
 
-        assert_eq!(solve_part1(&input_generator(EXAMPLE_INPUT)), 13140);
+        assert_eq!(solve_part1(&input_generator(EXAMPLE_INPUT).unwrap()), 13140);
     }
 
     #[test]
@@ -306,8 +315,20 @@ noop";
 #######.......#######.......#######.....";
 
         assert_eq!(
-            solve_part2(&input_generator(EXAMPLE_INPUT)),
+            solve_part2(&input_generator(EXAMPLE_INPUT).unwrap()),
             RENDERED_EXAMPLE
         );
     }
+
+    #[test]
+    fn invalid_instruction() {
+        assert_eq!(
+            input_generator("noop\naddx x").err(),
+            Some(ParseError::new(2, 6, "x", "addx operand"))
+        );
+        assert_eq!(
+            input_generator("mulx 3").err(),
+            Some(ParseError::new(1, 1, "mulx 3", "'noop' or 'addx'"))
+        );
+    }
 }
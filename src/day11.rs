@@ -1,3 +1,5 @@
+use crate::error::ParseError;
+
 type MonkeyOp = Box<dyn Fn(i64) -> i64>;
 
 enum MonkeyOperand {
@@ -20,48 +22,77 @@ pub struct Monkey {
     inspected: usize,
 }
 
-impl From<&str> for MonkeyOperand {
-    fn from(s: &str) -> Self {
+impl TryFrom<&str> for MonkeyOperand {
+    type Error = ();
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
         match s {
-            "old" => MonkeyOperand::Old,
-            n => MonkeyOperand::Literal(n.parse().unwrap()),
+            "old" => Ok(MonkeyOperand::Old),
+            n => n.parse().map(MonkeyOperand::Literal).map_err(|_| ()),
         }
     }
 }
 
-impl From<&str> for MonkeyOperator {
-    fn from(s: &str) -> Self {
+impl TryFrom<&str> for MonkeyOperator {
+    type Error = ();
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
         match s {
-            "+" => MonkeyOperator::Add,
-            "*" => MonkeyOperator::Mul,
-            _ => panic!("Unknown MonkeyOperator {}", s),
+            "+" => Ok(MonkeyOperator::Add),
+            "*" => Ok(MonkeyOperator::Mul),
+            _ => Err(()),
         }
     }
 }
 
-impl From<&str> for Monkey {
-    fn from(s: &str) -> Self {
-        let mut lines = s.lines();
+impl Monkey {
+    fn parse(s: &str, num_monkeys: usize) -> Result<Self, ParseError> {
+        let lines: Vec<_> = s.lines().collect();
+
+        // returns the remainder of line `i` after `prefix`
+        let field = |i: usize, prefix: &str| {
+            let line = lines.get(i).copied().unwrap_or(&s[s.len()..]);
+            line.trim_start()
+                .strip_prefix(prefix)
+                .map(|rest| (line, rest))
+                .ok_or_else(|| {
+                    ParseError::at(line, line.trim_start(), format!("'{prefix}'")).shift_lines(i)
+                })
+        };
+
+        let err = |i: usize, line: &str, part: &str, expected: &str| {
+            ParseError::at(line, part, expected).shift_lines(i)
+        };
 
         // don't need "Monkey 0:"
-        lines.next();
+        field(0, "Monkey ")?;
 
         // items
-        let mut parts = lines.next().unwrap().split(':');
-        parts.next();
-        let items = parts
-            .next()
-            .unwrap()
+        let (line, rest) = field(1, "Starting items: ")?;
+        let items = rest
             .split(',')
-            .map(|s| s.trim().parse().unwrap())
-            .collect();
+            .map(|s| {
+                let s = s.trim();
+                s.parse().map_err(|_| err(1, line, s, "worry level"))
+            })
+            .collect::<Result<_, _>>()?;
 
         // op
-        let mut parts = lines.next().unwrap().split_whitespace().skip(3);
-
-        let lhs = MonkeyOperand::from(parts.next().unwrap());
-        let operator = MonkeyOperator::from(parts.next().unwrap());
-        let rhs = MonkeyOperand::from(parts.next().unwrap());
+        let (line, rest) = field(2, "Operation: new = ")?;
+        let mut parts = rest.split_whitespace();
+        let mut next_part = |expected: &str| {
+            parts
+                .next()
+                .ok_or_else(|| err(2, line, &line[line.len()..], expected))
+        };
+
+        let part = next_part("operand")?;
+        let lhs = MonkeyOperand::try_from(part).map_err(|_| err(2, line, part, "operand"))?;
+        let part = next_part("'+' or '*'")?;
+        let operator =
+            MonkeyOperator::try_from(part).map_err(|_| err(2, line, part, "'+' or '*'"))?;
+        let part = next_part("operand")?;
+        let rhs = MonkeyOperand::try_from(part).map_err(|_| err(2, line, part, "operand"))?;
 
         let op = Box::new(move |n| {
             let lhs = match lhs {
@@ -80,32 +111,23 @@ impl From<&str> for Monkey {
         });
 
         //test
-        let divisor = lines
-            .next()
-            .unwrap()
-            .split_whitespace()
-            .last()
-            .unwrap()
-            .parse::<i64>()
-            .unwrap();
-
-        let true_monkey = lines
-            .next()
-            .unwrap()
-            .split_whitespace()
-            .last()
-            .unwrap()
-            .parse::<usize>()
-            .unwrap();
-
-        let false_monkey = lines
-            .next()
-            .unwrap()
-            .split_whitespace()
-            .last()
-            .unwrap()
-            .parse::<usize>()
-            .unwrap();
+        let (line, rest) = field(3, "Test: divisible by ")?;
+        let divisor = match rest.parse::<i64>() {
+            Ok(n) if n > 0 => n,
+            _ => return Err(err(3, line, rest, "positive divisor")),
+        };
+
+        let target = |i: usize, prefix: &str| {
+            let (line, rest) = field(i, prefix)?;
+            let expected = format!("monkey number below {num_monkeys}");
+            match rest.parse::<usize>() {
+                Ok(n) if n < num_monkeys => Ok(n),
+                _ => Err(err(i, line, rest, &expected)),
+            }
+        };
+
+        let true_monkey = target(4, "If true: throw to monkey ")?;
+        let false_monkey = target(5, "If false: throw to monkey ")?;
 
         let test = Box::new(move |n| {
             if n % divisor == 0 {
@@ -115,17 +137,15 @@ impl From<&str> for Monkey {
             }
         });
 
-        Self {
+        Ok(Self {
             items,
             op,
             test,
             divisor,
             inspected: 0,
-        }
+        })
     }
-}
 
-impl Monkey {
     fn take_turn(&mut self, worry_op: &dyn Fn(i64) -> i64) -> Vec<(usize, i64)> {
         std::mem::take(&mut self.items)
             .into_iter()
@@ -141,13 +161,34 @@ impl Monkey {
 }
 
 // #[aoc_generator(day11)]
-pub fn input_generator(input: &str) -> Vec<Monkey> {
-    input.split("\n\n").map(Monkey::from).collect()
+pub fn input_generator(input: &str) -> Result<Vec<Monkey>, ParseError> {
+    let chunks: Vec<_> = input.split("\n\n").collect();
+    let mut offset = 0;
+
+    let monkeys = chunks
+        .iter()
+        .map(|chunk| {
+            let monkey = Monkey::parse(chunk, chunks.len()).map_err(|e| e.shift_lines(offset));
+            offset += chunk.lines().count() + 1;
+            monkey
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // monkey business multiplies the two most active monkeys
+    if monkeys.len() < 2 {
+        return Err(ParseError::at(
+            input,
+            &input[input.len()..],
+            "blank line and a second monkey",
+        ));
+    }
+
+    Ok(monkeys)
 }
 
 #[aoc(day11, part1)]
-pub fn solve_part1(input: &str) -> usize {
-    let mut monkeys = input_generator(input);
+pub fn solve_part1(input: &str) -> Result<usize, ParseError> {
+    let mut monkeys = input_generator(input)?;
 
     for _ in 0..20 {
         for m in 0..monkeys.len() {
@@ -161,12 +202,12 @@ pub fn solve_part1(input: &str) -> usize {
     inspected.sort();
     let mut max_iter = inspected.iter().rev();
 
-    max_iter.next().unwrap() * max_iter.next().unwrap()
+    Ok(max_iter.next().unwrap() * max_iter.next().unwrap())
 }
 
 #[aoc(day11, part2)]
-pub fn solve_part2(input: &str) -> usize {
-    let mut monkeys = input_generator(input);
+pub fn solve_part2(input: &str) -> Result<usize, ParseError> {
+    let mut monkeys = input_generator(input)?;
 
     let modulo: i64 = monkeys.iter().map(|m| m.divisor).product();
 
@@ -182,7 +223,7 @@ pub fn solve_part2(input: &str) -> usize {
     inspected.sort();
     let mut max_iter = inspected.iter().rev();
 
-    max_iter.next().unwrap() * max_iter.next().unwrap()
+    Ok(max_iter.next().unwrap() * max_iter.next().unwrap())
 }
 
 #[cfg(test)]
@@ -226,7 +267,7 @@ Monkey 3:
     If true: throw to monkey 2
     If false: throw to monkey 3";
 
-        let m = Monkey::from(MONKEY);
+        let m = Monkey::parse(MONKEY, 4).unwrap();
 
         assert_eq!(m.items, vec![79, 98]);
         assert_eq!((m.op)(10), 190);
@@ -236,11 +277,40 @@ Monkey 3:
 
     #[test]
     fn examples_part1() {
-        assert_eq!(solve_part1(EXAMPLE_INPUT), 10605);
+        assert_eq!(solve_part1(EXAMPLE_INPUT), Ok(10605));
     }
 
     #[test]
     fn examples_part2() {
-        assert_eq!(solve_part2(EXAMPLE_INPUT), 2713310158);
+        assert_eq!(solve_part2(EXAMPLE_INPUT), Ok(2713310158));
+    }
+
+    #[test]
+    fn invalid_monkey() {
+        let input = EXAMPLE_INPUT.replace("new = old + 3", "new = old - 3");
+        assert_eq!(
+            input_generator(&input).err(),
+            Some(ParseError::new(24, 24, "- 3", "'+' or '*'"))
+        );
+
+        let input = EXAMPLE_INPUT.replace("throw to monkey 1", "throw to monkey 4");
+        assert_eq!(
+            input_generator(&input).err(),
+            Some(ParseError::new(19, 30, "4", "monkey number below 4"))
+        );
+    }
+
+    #[test]
+    fn one_monkey() {
+        let input = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 0
+    If false: throw to monkey 0";
+        assert_eq!(
+            solve_part1(input).err(),
+            Some(ParseError::new(6, 32, "", "blank line and a second monkey"))
+        );
     }
 }
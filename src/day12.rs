@@ -1,33 +1,8 @@
-use std::cmp::Ordering;
-use std::collections::BinaryHeap;
+use std::fmt;
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
-struct State {
-    cost: usize,
-    position: usize,
-}
-
-// The priority queue depends on `Ord`.
-// Explicitly implement the trait so the queue becomes a min-heap
-// instead of a max-heap.
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> Ordering {
-        // Notice that the we flip the ordering on costs.
-        // In case of a tie we compare positions - this step is necessary
-        // to make implementations of `PartialEq` and `Ord` consistent.
-        other
-            .cost
-            .cmp(&self.cost)
-            .then_with(|| self.position.cmp(&other.position))
-    }
-}
-
-// `PartialOrd` needs to be implemented as well.
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
+use crate::error::ParseError;
+use crate::grid::{Grid, Pos};
+use crate::search::{self, Cost, Graph};
 
 #[derive(Debug, PartialEq, Eq)]
 enum NodeKind {
@@ -39,50 +14,109 @@ enum NodeKind {
 #[derive(Debug)]
 struct Node {
     kind: NodeKind,
-    height: i8,
+    // `None` for walls, which can't be stepped on at all
+    height: Option<i8>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ClimbRules {
+    pub max_ascent: i8,
+    // `None` allows any drop
+    pub max_descent: Option<i8>,
+    pub diagonals: bool,
+    // by height difference, negative going down
+    pub step_cost: fn(i8) -> usize,
 }
 
 #[derive(Debug)]
 pub struct HeightMap {
-    nodes: Vec<Node>,
-    width: usize,
+    nodes: Grid<Node>,
+    rules: ClimbRules,
 }
 
-impl From<u8> for Node {
-    fn from(c: u8) -> Self {
+impl Default for ClimbRules {
+    // the puzzle's rules
+    fn default() -> Self {
+        Self {
+            max_ascent: 1,
+            max_descent: None,
+            diagonals: false,
+            step_cost: |_| 1,
+        }
+    }
+}
+
+impl TryFrom<u8> for Node {
+    type Error = u8;
+
+    fn try_from(c: u8) -> Result<Self, Self::Error> {
         match c {
-            b'S' => Self {
+            b'S' => Ok(Self {
                 kind: NodeKind::Start,
-                height: 0,
-            },
-            b'E' => Self {
+                height: Some(0),
+            }),
+            b'E' => Ok(Self {
                 kind: NodeKind::End,
-                height: (b'z' - b'a') as i8,
-            },
-            c @ b'a'..=b'z' => Self {
+                height: Some((b'z' - b'a') as i8),
+            }),
+            c @ b'a'..=b'z' => Ok(Self {
                 kind: NodeKind::Normal,
-                height: (c - b'a') as i8,
-            },
-            invalid => panic!("Invalid map height '{invalid}'"),
+                height: Some((c - b'a') as i8),
+            }),
+            b'#' => Ok(Self {
+                kind: NodeKind::Normal,
+                height: None,
+            }),
+            invalid => Err(invalid),
         }
     }
 }
 
-impl From<&str> for HeightMap {
-    fn from(s: &str) -> Self {
-        let width = s.lines().next().unwrap().len();
-        let nodes = s
-            .as_bytes()
-            .iter()
-            .copied()
-            .filter(|c| char::is_ascii_alphabetic(&(*c as char)))
-            .map(Node::from)
-            .collect();
+impl TryFrom<&str> for HeightMap {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let nodes = Grid::parse(
+            s,
+            |c| u8::try_from(c).ok().and_then(|c| Node::try_from(c).ok()),
+            "height 'a'..='z', 'S', 'E' or '#'",
+        )?;
 
-        Self { nodes, width }
+        Ok(Self {
+            nodes,
+            rules: ClimbRules::default(),
+        })
     }
 }
 
+// Start and End match any of the cells marked 'S' or 'E'
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endpoint {
+    Start,
+    End,
+    At(Pos),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    pub cost: usize,
+    // from the start to the end, including both
+    pub nodes: Vec<usize>,
+}
+
+// no end can be reached, from any start in part 1 or any lowest point in
+// part 2
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoRoute;
+
+impl fmt::Display for NoRoute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no route up to the end")
+    }
+}
+
+impl std::error::Error for NoRoute {}
+
 #[derive(Debug, PartialEq, Eq)]
 struct Edge {
     node: usize,
@@ -90,55 +124,80 @@ struct Edge {
 }
 
 impl HeightMap {
-    fn solve(&self) -> usize {
-        let adj_list = self.adjacency_list();
-        self.shortest_path(&adj_list, self.start_idx(), self.end_idx())
-            .unwrap()
+    fn solve(&self) -> Option<usize> {
+        self.distance(Endpoint::Start, Endpoint::End)
     }
 
-    fn solve_part2(&self) -> usize {
-        let adj_list = self.adjacency_list();
-        let goal = self.end_idx();
-        self.nodes
-            .iter()
-            .enumerate()
-            .filter(|(_i, v)| (v.height == 0))
-            .filter_map(|(i, _v)| self.shortest_path(&adj_list, i, goal))
-            .min()
-            .unwrap()
+    // search back from the ends to the closest lowest point
+    fn solve_part2(&self) -> Option<usize> {
+        let nodes = self.nodes.cells();
+        search::dijkstra_multi(&Reversed(self), self.ends(), |&node| {
+            nodes[node].height == Some(0)
+        })
+        .goal_distance()
+    }
+
+    pub fn distances_to_end(&self) -> Grid<Option<usize>> {
+        let paths = search::dijkstra_multi(&Reversed(self), self.ends(), |_| false);
+
+        Grid::from_vec(
+            self.nodes.width(),
+            (0..self.nodes.len()).map(|i| paths.distance(&i)).collect(),
+        )
     }
 
-    fn find_nodekind(&self, kind: NodeKind) -> usize {
+    fn find_nodekind(&self, kind: NodeKind) -> Vec<usize> {
         self.nodes
+            .cells()
             .iter()
             .enumerate()
-            .find(|(_i, n)| n.kind == kind)
-            .unwrap()
-            .0
+            .filter(|(_i, n)| n.kind == kind)
+            .map(|(i, _)| i)
+            .collect()
     }
 
-    fn start_idx(&self) -> usize {
+    pub fn starts(&self) -> Vec<usize> {
         self.find_nodekind(NodeKind::Start)
     }
 
-    fn end_idx(&self) -> usize {
+    pub fn ends(&self) -> Vec<usize> {
         self.find_nodekind(NodeKind::End)
     }
 
-    fn adjacency_list(&self) -> Vec<Vec<Edge>> {
-        (0..self.nodes.len())
-            .map(|i| self.edges_for_node(i))
-            .collect()
+    fn endpoint_nodes(&self, endpoint: Endpoint) -> Vec<usize> {
+        match endpoint {
+            Endpoint::Start => self.starts(),
+            Endpoint::End => self.ends(),
+            Endpoint::At(pos) => self
+                .nodes
+                .index_of(pos)
+                .filter(|&i| self.nodes.cells()[i].height.is_some())
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    pub fn with_rules(self, rules: ClimbRules) -> Self {
+        Self { rules, ..self }
+    }
+
+    fn climb(&self, from: usize, to: usize) -> Option<usize> {
+        let nodes = self.nodes.cells();
+        let delta = nodes[to].height? - nodes[from].height?;
+
+        let allowed = delta <= self.rules.max_ascent
+            && self.rules.max_descent.is_none_or(|max| -delta <= max);
+        allowed.then(|| (self.rules.step_cost)(delta))
     }
 
     fn edges_for_node(&self, node: usize) -> Vec<Edge> {
         let mut edges = Vec::new();
 
         for candidate in self.neighbors_for_node(node) {
-            if self.nodes[candidate].height <= self.nodes[node].height + 1 {
+            if let Some(cost) = self.climb(node, candidate) {
                 edges.push(Edge {
                     node: candidate,
-                    cost: 1,
+                    cost,
                 })
             }
         }
@@ -146,93 +205,132 @@ impl HeightMap {
         edges
     }
 
-    fn neighbors_for_node(&self, node: usize) -> Vec<usize> {
-        let mut neighbors = Vec::new();
-
-        // up?
-        if node >= self.width {
-            neighbors.push(node - self.width)
-        }
+    // `Edge::node` is where each edge comes from
+    fn edges_into_node(&self, node: usize) -> Vec<Edge> {
+        let mut edges = Vec::new();
 
-        // left?
-        if node % self.width != 0 {
-            neighbors.push(node - 1);
+        for candidate in self.neighbors_for_node(node) {
+            if let Some(cost) = self.climb(candidate, node) {
+                edges.push(Edge {
+                    node: candidate,
+                    cost,
+                })
+            }
         }
 
-        // right?
-        if node % self.width != self.width - 1 {
-            neighbors.push(node + 1);
-        }
+        edges
+    }
 
-        // down?
-        let down = node + self.width;
-        if down < self.nodes.len() {
-            neighbors.push(down)
-        }
+    fn neighbors_for_node(&self, node: usize) -> Vec<usize> {
+        let pos = self.nodes.pos_of(node);
+        let neighbors: Vec<_> = if self.rules.diagonals {
+            self.nodes.neighbors8(pos).collect()
+        } else {
+            self.nodes.neighbors4(pos).collect()
+        };
 
         neighbors
+            .into_iter()
+            .filter_map(|pos| self.nodes.index_of(pos))
+            .collect()
     }
 
-    fn shortest_path(&self, adj_list: &[Vec<Edge>], start: usize, end: usize) -> Option<usize> {
-        // dist[node] = current shortest distance from `start` to `node`
-        let mut dist: Vec<_> = (0..adj_list.len()).map(|_| usize::MAX).collect();
+    fn search(&self, from: Endpoint, to: Endpoint) -> search::Paths<usize> {
+        let goals = self.endpoint_nodes(to);
+        search::dijkstra_multi(self, self.endpoint_nodes(from), |node| goals.contains(node))
+    }
 
-        let mut heap = BinaryHeap::new();
+    pub fn distance(&self, from: Endpoint, to: Endpoint) -> Option<usize> {
+        self.search(from, to).goal_distance()
+    }
 
-        // We're at `start`, with a zero cost
-        dist[start] = 0;
-        heap.push(State {
-            cost: 0,
-            position: start,
-        });
+    pub fn shortest_route(&self, from: Endpoint, to: Endpoint) -> Option<Route> {
+        let paths = self.search(from, to);
 
-        // Examine the frontier with lower cost nodes first (min-heap)
-        while let Some(State { cost, position }) = heap.pop() {
-            // Alternatively we could have continued to find all shortest paths
-            if position == end {
-                return Some(cost);
-            }
+        Some(Route {
+            cost: paths.goal_distance()?,
+            nodes: paths.path_to(paths.goal.as_ref()?)?,
+        })
+    }
 
-            // Important as we may have already found a better way
-            if cost > dist[position] {
-                continue;
-            }
+    // arrows along the route as in the puzzle description
+    pub fn render_route(&self, route: &Route) -> String {
+        let mut drawing = Grid::new(self.nodes.width(), self.nodes.height(), '.');
+
+        for step in route.nodes.windows(2) {
+            let (from, to) = (self.nodes.pos_of(step[0]), self.nodes.pos_of(step[1]));
+            drawing[from] = match (
+                to.0 as isize - from.0 as isize,
+                to.1 as isize - from.1 as isize,
+            ) {
+                (0, -1) => '^',
+                (0, 1) => 'v',
+                (-1, 0) => '<',
+                (1, 0) => '>',
+                (-1, -1) | (1, 1) => '\\',
+                (1, -1) | (-1, 1) => '/',
+                _ => '?',
+            };
+        }
 
-            // For each node we can reach, see if we can find a way with
-            // a lower cost going through this node
-            for edge in &adj_list[position] {
-                let next = State {
-                    cost: cost + edge.cost,
-                    position: edge.node,
-                };
-
-                // If so, add it to the frontier and continue
-                if next.cost < dist[next.position] {
-                    heap.push(next);
-                    // Relaxation, we have now found a better way
-                    dist[next.position] = next.cost;
-                }
-            }
+        if let Some(&last) = route.nodes.last() {
+            drawing[self.nodes.pos_of(last)] = 'E';
         }
 
-        // Goal not reachable
-        None
+        drawing.render(|&c| c)
+    }
+}
+
+impl Graph for HeightMap {
+    type Node = usize;
+
+    fn neighbors(&self, node: &usize) -> impl Iterator<Item = (usize, Cost)> {
+        self.edges_for_node(*node)
+            .into_iter()
+            .map(|e| (e.node, e.cost))
+    }
+}
+
+// the height map with every edge turned around
+struct Reversed<'a>(&'a HeightMap);
+
+impl Graph for Reversed<'_> {
+    type Node = usize;
+
+    fn neighbors(&self, node: &usize) -> impl Iterator<Item = (usize, Cost)> {
+        self.0
+            .edges_into_node(*node)
+            .into_iter()
+            .map(|e| (e.node, e.cost))
     }
 }
 
 #[aoc_generator(day12)]
-pub fn input_generator(input: &str) -> HeightMap {
-    HeightMap::from(input)
+pub fn input_generator(input: &str) -> Result<HeightMap, ParseError> {
+    let map = HeightMap::try_from(input)?;
+
+    for (kind, marker) in [(NodeKind::Start, "'S'"), (NodeKind::End, "'E'")] {
+        if !map.nodes.cells().iter().any(|n| n.kind == kind) {
+            return Err(ParseError::new(
+                1,
+                1,
+                input.lines().next().unwrap_or(""),
+                format!("at least one {marker} in map"),
+            ));
+        }
+    }
+
+    Ok(map)
 }
 
 #[aoc(day12, part1)]
-pub fn solve_part1(input: &HeightMap) -> usize {
-    input.solve()
+pub fn solve_part1(input: &HeightMap) -> Result<usize, NoRoute> {
+    input.solve().ok_or(NoRoute)
 }
 
 #[aoc(day12, part2)]
-pub fn solve_part2(input: &HeightMap) -> usize {
-    input.solve_part2()
+pub fn solve_part2(input: &HeightMap) -> Result<usize, NoRoute> {
+    input.solve_part2().ok_or(NoRoute)
 }
 
 #[cfg(test)]
@@ -247,12 +345,230 @@ abdefghi";
 
     #[test]
     fn examples_part1() {
-        assert_eq!(solve_part1(&input_generator(EXAMPLE_INPUT)), 31);
+        assert_eq!(
+            solve_part1(&input_generator(EXAMPLE_INPUT).unwrap()),
+            Ok(31)
+        );
     }
 
     #[test]
     fn examples_part2() {
-        assert_eq!(solve_part2(&input_generator(EXAMPLE_INPUT)), 29);
+        assert_eq!(
+            solve_part2(&input_generator(EXAMPLE_INPUT).unwrap()),
+            Ok(29)
+        );
+    }
+
+    #[test]
+    fn shortest_route() {
+        let map = input_generator(EXAMPLE_INPUT).unwrap();
+        let route = map.shortest_route(Endpoint::Start, Endpoint::End).unwrap();
+
+        assert_eq!(route.cost, 31);
+        assert_eq!(route.nodes.len(), 32);
+        assert_eq!(route.nodes.first(), Some(&map.starts()[0]));
+        assert_eq!(route.nodes.last(), Some(&map.ends()[0]));
+        assert!(route.nodes.windows(2).all(|step| map
+            .edges_for_node(step[0])
+            .iter()
+            .any(|e| e.node == step[1])));
+
+        let drawing = map.render_route(&route);
+        assert_eq!(drawing.lines().count(), 5);
+        assert_eq!(drawing.chars().filter(|c| "^v<>".contains(*c)).count(), 31);
+    }
+
+    #[test]
+    fn render_route() {
+        // the only way down to the second row is at the far end
+        let map = input_generator("Sbcdefghijklm\nEyxwvutsrqpon").unwrap();
+        let route = map.shortest_route(Endpoint::Start, Endpoint::End).unwrap();
+
+        assert_eq!(route.cost, 25);
+        assert_eq!(map.render_route(&route), ">>>>>>>>>>>>v\nE<<<<<<<<<<<<");
+    }
+
+    #[test]
+    fn climb_rules() {
+        let map = || input_generator(EXAMPLE_INPUT).unwrap();
+        let rules = ClimbRules::default();
+        let cost = |map: &HeightMap| map.distance(Endpoint::Start, Endpoint::End);
+
+        assert_eq!(cost(&map()), Some(31));
+
+        // nothing too steep to climb, so straight there
+        let map = map().with_rules(ClimbRules {
+            max_ascent: 25,
+            ..rules
+        });
+        assert_eq!(cost(&map), Some(7));
+
+        let map = map.with_rules(ClimbRules {
+            max_ascent: 25,
+            diagonals: true,
+            ..rules
+        });
+        assert_eq!(cost(&map), Some(5));
+
+        // twice as hard going up
+        let corridor = input_generator("Sbcdefghijklm\nEyxwvutsrqpon").unwrap();
+        let corridor = corridor.with_rules(ClimbRules {
+            step_cost: |delta| if delta > 0 { 2 } else { 1 },
+            ..rules
+        });
+        assert_eq!(cost(&corridor), Some(50));
+        assert_eq!(
+            corridor.edges_for_node(1),
+            vec![Edge { node: 0, cost: 1 }, Edge { node: 2, cost: 2 }]
+        );
+    }
+
+    #[test]
+    fn max_descent() {
+        let map = HeightMap::try_from("aaa\naza\naaa").unwrap();
+        assert_eq!(map.edges_for_node(4).len(), 4);
+
+        let map = map.with_rules(ClimbRules {
+            max_descent: Some(1),
+            ..ClimbRules::default()
+        });
+        assert_eq!(map.edges_for_node(4), vec![]);
+        assert_eq!(map.edges_into_node(4), vec![]);
+    }
+
+    #[test]
+    fn distances_to_end() {
+        let map = input_generator(EXAMPLE_INPUT).unwrap();
+        let distances = map.distances_to_end();
+
+        assert_eq!(distances[(5, 2)], Some(0));
+        assert_eq!(distances[(0, 0)], Some(31));
+        assert_eq!(distances[(0, 4)], Some(29));
+
+        // every lowest point going the long way round still makes it
+        assert!(map
+            .nodes
+            .iter()
+            .filter(|(_, n)| n.height == Some(0))
+            .all(|(pos, _)| distances[pos].is_some_and(|d| d >= 29)));
+
+        // can't climb out of a pit
+        let map = input_generator("SazE").unwrap();
+        assert_eq!(
+            map.distances_to_end().cells(),
+            [None, None, Some(1), Some(0)]
+        );
+    }
+
+    #[test]
+    fn several_starts_and_ends() {
+        let map = input_generator("Saaaaa\naaaaaa\naaaaaE\nEaaaaS").unwrap();
+        assert_eq!(map.starts(), vec![0, 23]);
+        assert_eq!(map.ends(), vec![17, 18]);
+
+        // too steep to climb up to either end
+        assert_eq!(map.distance(Endpoint::Start, Endpoint::End), None);
+
+        // the nearest start and the nearest end win
+        let map = map.with_rules(ClimbRules {
+            max_ascent: 25,
+            ..ClimbRules::default()
+        });
+        assert_eq!(solve_part1(&map), Ok(1));
+        assert_eq!(solve_part2(&map), Ok(1));
+        assert_eq!(map.distance(Endpoint::At((0, 0)), Endpoint::End), Some(3));
+        assert_eq!(map.distance(Endpoint::Start, Endpoint::At((2, 0))), Some(2));
+
+        let route = map
+            .shortest_route(Endpoint::At((3, 0)), Endpoint::End)
+            .unwrap();
+        assert_eq!(route.cost, 4);
+        assert_eq!(route.nodes.last(), Some(&17));
+    }
+
+    #[test]
+    fn walls() {
+        let rules = ClimbRules {
+            max_ascent: 25,
+            ..ClimbRules::default()
+        };
+
+        let open = input_generator("Saaa\naaaa\nEaaa").unwrap();
+        assert_eq!(solve_part1(&open.with_rules(rules)), Ok(2));
+
+        // the wall forces the long way round
+        let walled = input_generator("Saaa\n###a\nEaaa").unwrap();
+        assert_eq!(walled.edges_for_node(4), vec![]);
+        assert_eq!(walled.edges_into_node(4), vec![]);
+        assert_eq!(walled.neighbors_for_node(0), vec![1, 4]);
+        assert_eq!(walled.distance(Endpoint::Start, Endpoint::At((0, 1))), None);
+        assert_eq!(solve_part1(&walled.with_rules(rules)), Ok(8));
+
+        // walls have no height, so only the start counts as lowest
+        let walled = input_generator("Sbbb\n###b\nEbbb").unwrap();
+        assert_eq!(solve_part2(&walled.with_rules(rules)), Ok(8));
+
+        let blocked = input_generator("S#E").unwrap();
+        assert_eq!(solve_part1(&blocked), Err(NoRoute));
+        assert_eq!(solve_part2(&blocked), Err(NoRoute));
+    }
+
+    #[test]
+    fn distance() {
+        let map = input_generator(EXAMPLE_INPUT).unwrap();
+
+        assert_eq!(
+            map.distance(Endpoint::Start, Endpoint::At((5, 2))),
+            Some(31)
+        );
+        assert_eq!(map.distance(Endpoint::At((0, 0)), Endpoint::End), Some(31));
+        assert_eq!(
+            map.distance(Endpoint::At((0, 4)), Endpoint::At((0, 4))),
+            Some(0)
+        );
+        assert_eq!(
+            map.distance(Endpoint::At((1, 0)), Endpoint::At((0, 2))),
+            Some(3)
+        );
+
+        // any drop is fine, so it's straight back down, but nothing off the map
+        assert_eq!(map.distance(Endpoint::End, Endpoint::Start), Some(7));
+        assert_eq!(map.distance(Endpoint::Start, Endpoint::At((8, 0))), None);
+    }
+
+    #[test]
+    fn test_edges_into_node() {
+        let map = HeightMap::try_from("abc\nbcd\nzzz").unwrap();
+        assert_eq!(
+            map.edges_into_node(4),
+            vec![
+                Edge { node: 1, cost: 1 },
+                Edge { node: 3, cost: 1 },
+                Edge { node: 5, cost: 1 },
+                Edge { node: 7, cost: 1 },
+            ]
+        );
+        assert_eq!(
+            map.edges_into_node(0),
+            vec![Edge { node: 1, cost: 1 }, Edge { node: 3, cost: 1 }]
+        );
+    }
+
+    #[test]
+    fn invalid_map() {
+        assert_eq!(
+            input_generator("Sab\nab!\nacE").err(),
+            Some(ParseError::new(
+                2,
+                3,
+                "!",
+                "height 'a'..='z', 'S', 'E' or '#'"
+            ))
+        );
+        assert_eq!(
+            input_generator("Sab\nabc\nacc").err(),
+            Some(ParseError::new(1, 1, "Sab", "at least one 'E' in map"))
+        );
     }
 
     #[test]
@@ -261,7 +577,7 @@ abdefghi";
 	     aaa\n\
 	     aaa";
 
-        let map = HeightMap::from(txt);
+        let map = HeightMap::try_from(txt).unwrap();
         assert_eq!(map.neighbors_for_node(0), vec![1, 3]);
         assert_eq!(map.neighbors_for_node(1), vec![0, 2, 4]);
         assert_eq!(map.neighbors_for_node(2), vec![1, 5]);
@@ -279,14 +595,14 @@ abdefghi";
 	     zaz\n\
 	     zzz";
 
-        let map = HeightMap::from(none);
+        let map = HeightMap::try_from(none).unwrap();
         assert_eq!(map.edges_for_node(4), vec![]);
 
         let all = "aaa\n\
 		   aza\n\
 		   aaa";
 
-        let map = HeightMap::from(all);
+        let map = HeightMap::try_from(all).unwrap();
         assert_eq!(
             map.edges_for_node(4),
             vec![
@@ -300,7 +616,7 @@ abdefghi";
         let partial_example = "Sab\n\
 			       abc\n\
 			       acc";
-        let map = HeightMap::from(partial_example);
+        let map = HeightMap::try_from(partial_example).unwrap();
         assert_eq!(
             map.edges_for_node(0),
             vec![Edge { node: 1, cost: 1 }, Edge { node: 3, cost: 1 },]
@@ -10,6 +10,8 @@ use nom::{
     IResult,
 };
 
+use crate::error::{self, ParseError};
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 enum Packet {
     List(Vec<Packet>),
@@ -76,8 +78,8 @@ impl PacketPairList {
 }
 
 #[aoc_generator(day13)]
-pub fn input_generator(input: &str) -> PacketPairList {
-    PacketPairList::parse(input).unwrap().1
+pub fn input_generator(input: &str) -> Result<PacketPairList, ParseError> {
+    error::finish(input, PacketPairList::parse(input), "packet")
 }
 
 #[aoc(day13, part1)]
@@ -141,11 +143,19 @@ mod tests {
 
     #[test]
     fn examples_part1() {
-        assert_eq!(solve_part1(&input_generator(EXAMPLE_INPUT)), 13);
+        assert_eq!(solve_part1(&input_generator(EXAMPLE_INPUT).unwrap()), 13);
     }
 
     #[test]
     fn examples_part2() {
-        assert_eq!(solve_part2(&input_generator(EXAMPLE_INPUT)), 140);
+        assert_eq!(solve_part2(&input_generator(EXAMPLE_INPUT).unwrap()), 140);
+    }
+
+    #[test]
+    fn invalid_packet() {
+        assert_eq!(
+            input_generator("[1,1]\n[2]\n\n[1,x]\n[3]").err(),
+            Some(ParseError::new(4, 1, "[1,x]", "packet"))
+        );
     }
 }
@@ -1,19 +1,14 @@
-use std::collections::HashSet;
-
+use itertools::Itertools;
 use nom::{
-    bytes::complete::tag,
-    character::complete::{newline, u64},
-    combinator::map,
-    multi::separated_list1,
-    sequence::separated_pair,
-    IResult,
+    bytes::complete::tag, character::complete::u64, combinator::map, multi::separated_list1,
+    sequence::separated_pair, IResult,
 };
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct Point {
-    x: usize,
-    y: usize,
-}
+use crate::error::{self, ParseError};
+use crate::grid::Grid;
+use crate::point;
+
+type Point = point::Point<usize>;
 
 struct Line(Point, Point);
 
@@ -23,18 +18,15 @@ struct Rock(Vec<Point>);
 #[derive(Debug)]
 pub struct RockList(Vec<Rock>);
 
-impl Point {
-    fn parse(input: &str) -> IResult<&str, Self> {
-        map(separated_pair(u64, tag(","), u64), |(x, y)| Self {
-            x: x as usize,
-            y: y as usize,
-        })(input)
-    }
+fn parse_point(input: &str) -> IResult<&str, Point> {
+    map(separated_pair(u64, tag(","), u64), |(x, y)| {
+        Point::new(x as usize, y as usize)
+    })(input)
 }
 
 impl Rock {
     fn parse(input: &str) -> IResult<&str, Self> {
-        map(separated_list1(tag(" -> "), Point::parse), Self)(input)
+        map(separated_list1(tag(" -> "), parse_point), Self)(input)
     }
 
     fn points(&self) -> Box<dyn Iterator<Item = Point> + '_> {
@@ -47,10 +39,6 @@ impl Rock {
 }
 
 impl RockList {
-    fn parse(input: &str) -> IResult<&str, Self> {
-        map(separated_list1(newline, Rock::parse), Self)(input)
-    }
-
     fn points(&self) -> Box<dyn Iterator<Item = Point> + '_> {
         Box::new(self.0.iter().flat_map(Rock::points))
     }
@@ -84,30 +72,57 @@ impl Line {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Air,
+    Rock,
+    Sand,
+}
+
 #[derive(Debug)]
 enum Bottom {
     EndlessVoid,
     Floor,
 }
 
+// positions here are in grid coordinates, which are x shifted so the sand
+// can't fall off the left edge
 #[derive(Debug)]
 struct State {
-    objects: HashSet<Point>,
+    grid: Grid<Cell>,
+    // where the sand comes in
+    source: Point,
     highest_y: usize,
     bottom: Bottom,
 }
 
+const SOURCE: Point = Point { x: 500, y: 0 };
+
 impl From<&RockList> for State {
     fn from(rocks: &RockList) -> Self {
-        let mut highest_y = usize::MIN;
+        let highest_y = rocks.points().map(|p| p.y).max().unwrap_or(usize::MIN);
 
-        let objects = rocks
+        // sand moves at most one column per row it falls, so including the
+        // floor it can't get further than this from the source. Everything
+        // is shifted right by as much, since that can be left of x = 0.
+        let spread = highest_y + 2;
+        let (min_x, max_x) = rocks
             .points()
-            .inspect(|p| highest_y = highest_y.max(p.y))
-            .collect();
+            .map(|p| p.x + spread)
+            .chain([SOURCE.x, SOURCE.x + 2 * spread])
+            .minmax()
+            .into_option()
+            .unwrap();
+        let column = |x: usize| x + spread - min_x;
+
+        let mut grid = Grid::new(max_x - min_x + 1, highest_y + 2, Cell::Air);
+        for p in rocks.points() {
+            grid[(column(p.x), p.y)] = Cell::Rock;
+        }
 
         Self {
-            objects,
+            grid,
+            source: Point::new(column(SOURCE.x), SOURCE.y),
             highest_y,
             bottom: Bottom::EndlessVoid,
         }
@@ -126,8 +141,16 @@ impl Iterator for State {
 }
 
 impl State {
+    fn is_blocked(&self, Point { x, y }: Point) -> bool {
+        self.grid[(x, y)] != Cell::Air
+    }
+
+    fn settle(&mut self, Point { x, y }: Point) {
+        self.grid[(x, y)] = Cell::Sand;
+    }
+
     fn next_endless_void(&mut self) -> Option<Point> {
-        let mut pos = Point { x: 500, y: 0 };
+        let mut pos = self.source;
 
         loop {
             // abyss-bound
@@ -137,22 +160,22 @@ impl State {
 
             // settled
             let next_y = pos.y + 1;
-            if self.objects.contains(&Point {
+            if self.is_blocked(Point {
                 x: pos.x,
                 y: next_y,
-            }) && self.objects.contains(&Point {
+            }) && self.is_blocked(Point {
                 x: pos.x - 1,
                 y: next_y,
-            }) && self.objects.contains(&Point {
+            }) && self.is_blocked(Point {
                 x: pos.x + 1,
                 y: next_y,
             }) {
-                self.objects.insert(pos);
+                self.settle(pos);
                 return Some(pos);
             };
 
             // move down?
-            if !self.objects.contains(&Point {
+            if !self.is_blocked(Point {
                 x: pos.x,
                 y: next_y,
             }) {
@@ -161,7 +184,7 @@ impl State {
             }
 
             // move down+left?
-            if !self.objects.contains(&Point {
+            if !self.is_blocked(Point {
                 x: pos.x - 1,
                 y: next_y,
             }) {
@@ -171,7 +194,7 @@ impl State {
             }
 
             // move down+right?
-            if !self.objects.contains(&Point {
+            if !self.is_blocked(Point {
                 x: pos.x + 1,
                 y: next_y,
             }) {
@@ -185,38 +208,38 @@ impl State {
     }
 
     fn next_floor(&mut self) -> Option<Point> {
-        let mut pos = Point { x: 500, y: 0 };
+        let mut pos = self.source;
 
         // source blocked
-        if self.objects.contains(&pos) {
+        if self.is_blocked(pos) {
             return None;
         }
 
         loop {
             // settled on the floor
             if pos.y == self.highest_y + 1 {
-                self.objects.insert(pos);
+                self.settle(pos);
                 return Some(pos);
             }
 
             // settled above the floor
             let next_y = pos.y + 1;
-            if self.objects.contains(&Point {
+            if self.is_blocked(Point {
                 x: pos.x,
                 y: next_y,
-            }) && self.objects.contains(&Point {
+            }) && self.is_blocked(Point {
                 x: pos.x - 1,
                 y: next_y,
-            }) && self.objects.contains(&Point {
+            }) && self.is_blocked(Point {
                 x: pos.x + 1,
                 y: next_y,
             }) {
-                self.objects.insert(pos);
+                self.settle(pos);
                 return Some(pos);
             };
 
             // move down?
-            if !self.objects.contains(&Point {
+            if !self.is_blocked(Point {
                 x: pos.x,
                 y: next_y,
             }) {
@@ -225,7 +248,7 @@ impl State {
             }
 
             // move down+left?
-            if !self.objects.contains(&Point {
+            if !self.is_blocked(Point {
                 x: pos.x - 1,
                 y: next_y,
             }) {
@@ -235,7 +258,7 @@ impl State {
             }
 
             // move down+right?
-            if !self.objects.contains(&Point {
+            if !self.is_blocked(Point {
                 x: pos.x + 1,
                 y: next_y,
             }) {
@@ -250,8 +273,8 @@ impl State {
 }
 
 #[aoc_generator(day14)]
-pub fn input_generator(input: &str) -> RockList {
-    RockList::parse(input).unwrap().1
+pub fn input_generator(input: &str) -> Result<RockList, ParseError> {
+    error::parse_lines(input, Rock::parse, "rock path 'x,y -> x,y'").map(RockList)
 }
 
 #[aoc(day14, part1)]
@@ -276,11 +299,26 @@ mod tests {
 
     #[test]
     fn examples_part1() {
-        assert_eq!(solve_part1(&input_generator(EXAMPLE_INPUT)), 24);
+        assert_eq!(solve_part1(&input_generator(EXAMPLE_INPUT).unwrap()), 24);
     }
 
     #[test]
     fn examples_part2() {
-        assert_eq!(solve_part2(&input_generator(EXAMPLE_INPUT)), 93);
+        assert_eq!(solve_part2(&input_generator(EXAMPLE_INPUT).unwrap()), 93);
+    }
+
+    #[test]
+    fn deep_rock() {
+        // deep enough for the sand to spread left of x = 0
+        let input = input_generator("500,600 -> 502,600").unwrap();
+        assert_eq!(solve_part1(&input), 0);
+    }
+
+    #[test]
+    fn invalid_rock() {
+        assert_eq!(
+            input_generator("498,4 -> 498,6\n503,4 -> 502").err(),
+            Some(ParseError::new(2, 7, "-> 502", "rock path 'x,y -> x,y'"))
+        );
     }
 }
@@ -1,24 +1,104 @@
+use std::fmt;
 use std::ops::RangeInclusive;
 
 use nom::{
+    branch::alt,
     bytes::complete::tag,
-    character::complete::{i64, newline},
+    character::complete::i64,
     combinator::map,
-    multi::separated_list0,
-    sequence::{preceded, separated_pair},
+    sequence::{delimited, preceded, separated_pair},
     IResult,
 };
 
-#[derive(Debug, PartialEq, Eq)]
-struct Point {
-    x: i64,
-    y: i64,
-}
+use crate::error::{self, ParseError};
+use crate::grid::Grid;
+use crate::point;
+
+type Point = point::Point<i64>;
 
 #[derive(Debug)]
 pub struct Puzzle {
     input: SensorBeaconList,
-    target_y: i64,
+    settings: Settings,
+}
+
+// differs between the example and the real input, e.g. a header of
+// "row y=10", "region x=0..=20, y=0..=20" and "tuning x*4000000+y"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Settings {
+    pub target_y: i64,
+    // inclusive corners of where part 2 looks
+    pub region: (Point, Point),
+    pub tuning: i64,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            target_y: 2_000_000,
+            region: (Point::new(0, 0), Point::new(4_000_000, 4_000_000)),
+            tuning: 4_000_000,
+        }
+    }
+}
+
+impl Settings {
+    fn in_region(&self, p: &Point) -> bool {
+        let (min, max) = &self.region;
+        (min.x..=max.x).contains(&p.x) && (min.y..=max.y).contains(&p.y)
+    }
+
+    fn tuning_frequency(&self, p: &Point) -> i64 {
+        p.x * self.tuning + p.y
+    }
+}
+
+// part 2 found nowhere in the search region for the distress beacon
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoDistressBeacon;
+
+impl fmt::Display for NoDistressBeacon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "no single uncovered cell for the distress beacon in the search region"
+        )
+    }
+}
+
+impl std::error::Error for NoDistressBeacon {}
+
+#[derive(Debug)]
+enum Setting {
+    Row(i64),
+    Region(Point, Point),
+    Tuning(i64),
+}
+
+impl Setting {
+    fn parse(input: &str) -> IResult<&str, Self> {
+        let range = || separated_pair(i64, tag("..="), i64);
+
+        alt((
+            map(preceded(tag("row y="), i64), Self::Row),
+            map(
+                preceded(
+                    tag("region x="),
+                    separated_pair(range(), tag(", y="), range()),
+                ),
+                |((x0, x1), (y0, y1))| Self::Region(Point::new(x0, y0), Point::new(x1, y1)),
+            ),
+            map(delimited(tag("tuning x*"), i64, tag("+y")), Self::Tuning),
+        ))(input)
+    }
+
+    fn apply(self, settings: &mut Settings) {
+        match self {
+            Self::Row(y) => settings.target_y = y,
+            Self::Region(min, max) => settings.region = (min, max),
+            Self::Tuning(tuning) => settings.tuning = tuning,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -27,10 +107,6 @@ struct SensorBeaconList(Vec<SensorBeaconPair>);
 struct SensorBeaconPair(Point, Point);
 
 impl SensorBeaconList {
-    fn parse(input: &str) -> IResult<&str, Self> {
-        map(separated_list0(newline, SensorBeaconPair::parse), Self)(input)
-    }
-
     fn iter(&self) -> impl Iterator<Item = &SensorBeaconPair> {
         self.0.iter()
     }
@@ -41,7 +117,7 @@ impl SensorBeaconPair {
         map(
             preceded(
                 tag("Sensor at "),
-                separated_pair(Point::parse, tag(": closest beacon is at "), Point::parse),
+                separated_pair(parse_point, tag(": closest beacon is at "), parse_point),
             ),
             |(p1, p2)| Self(p1, p2),
         )(input)
@@ -51,20 +127,13 @@ impl SensorBeaconPair {
         self.0.manhattan_distance(&self.1)
     }
 
-    fn known_at(&self, y: i64) -> RangeInclusive<i64> {
-        let mhd = self.manhattan_distance();
-        let delta_y = (self.0.y - y).abs();
-        let min = self.0.x - mhd + delta_y;
-        let max = self.0.x + mhd - delta_y;
-        min..=max
+    fn known_at(&self, y: i64) -> Option<RangeInclusive<i64>> {
+        let reach = self.manhattan_distance() - (self.0.y - y).abs();
+        (reach >= 0).then(|| self.0.x - reach..=self.0.x + reach)
     }
 
     fn can_exclude(&self, &Point { x, y }: &Point) -> bool {
-        let mhd = self.manhattan_distance();
-        let delta_y = (self.0.y - y).abs();
-        let min = self.0.x - mhd + delta_y;
-        let max = self.0.x + mhd - delta_y;
-        (min..=max).contains(&x)
+        self.known_at(y).is_some_and(|range| range.contains(&x))
     }
 
     fn perimeter_points(&self) -> impl Iterator<Item = Point> {
@@ -91,45 +160,133 @@ impl SensorBeaconPair {
     }
 }
 
-impl Point {
-    fn parse(input: &str) -> IResult<&str, Self> {
-        map(
-            preceded(tag("x="), separated_pair(i64, tag(", y="), i64)),
-            |(x, y)| Self { x, y },
-        )(input)
-    }
-
-    fn manhattan_distance(&self, other: &Self) -> i64 {
-        (self.x - other.x).abs() + (self.y - other.y).abs()
-    }
+fn parse_point(input: &str) -> IResult<&str, Point> {
+    map(
+        preceded(tag("x="), separated_pair(i64, tag(", y="), i64)),
+        |(x, y)| Point::new(x, y),
+    )(input)
 }
 
 impl Puzzle {
-    fn solve(&self) -> usize {
-        let range = self
+    // sorted, with gaps between them
+    pub fn covered(&self, y: i64) -> Vec<RangeInclusive<i64>> {
+        let mut ranges = self
             .input
             .iter()
-            .map(|sbp| sbp.known_at(self.target_y))
-            .reduce(|acc, r| *acc.start().min(r.start())..=*acc.end().max(r.end()))
-            .unwrap();
+            .filter_map(|sbp| sbp.known_at(y))
+            .collect::<Vec<_>>();
+        ranges.sort_by_key(|r| *r.start());
+
+        let mut merged: Vec<RangeInclusive<i64>> = Vec::new();
+        for range in ranges {
+            match merged.last_mut() {
+                // overlapping or right next to each other
+                Some(last) if *range.start() <= last.end() + 1 => {
+                    if range.end() > last.end() {
+                        *last = *last.start()..=*range.end();
+                    }
+                }
+                _ => merged.push(range),
+            }
+        }
+
+        merged
+    }
+
+    pub fn uncovered(&self, y: i64, xs: RangeInclusive<i64>) -> Vec<RangeInclusive<i64>> {
+        let mut gaps = Vec::new();
+        let mut next = *xs.start();
+
+        for range in self.covered(y) {
+            if next > *xs.end() {
+                break;
+            }
+
+            let gap = next..=(range.start() - 1).min(*xs.end());
+            if !gap.is_empty() {
+                gaps.push(gap);
+            }
+            next = next.max(range.end() + 1);
+        }
+
+        if next <= *xs.end() {
+            gaps.push(next..=*xs.end());
+        }
+
+        gaps
+    }
+
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    fn solve(&self) -> usize {
+        let target_y = self.settings.target_y;
+        let covered = self.covered(target_y);
 
         // remove any known beacons
         let mut beacons = self
             .input
             .iter()
             .filter_map(|SensorBeaconPair(_, Point { x, y })| {
-                (*y == self.target_y && range.contains(x)).then_some((x, y))
+                (*y == target_y && covered.iter().any(|r| r.contains(x))).then_some((x, y))
             })
             .collect::<Vec<_>>();
         beacons.sort();
         beacons.dedup();
 
-        (range.end() - range.start() + 1) as usize - beacons.len()
+        let cells: i64 = covered.iter().map(|r| r.end() - r.start() + 1).sum();
+        cells as usize - beacons.len()
+    }
+
+    // runs of rows with the same gaps make a single rectangle
+    pub fn uncovered_region(&self, (min, max): (Point, Point)) -> Vec<(Point, Point)> {
+        let mut rectangles = Vec::new();
+        // rectangles reaching down to the previous row
+        let mut open: Vec<(Point, Point)> = Vec::new();
+
+        for y in min.y..=max.y {
+            let mut still_open = Vec::new();
+
+            for gap in self.uncovered(y, min.x..=max.x) {
+                let (start, end) = (*gap.start(), *gap.end());
+
+                match open
+                    .iter()
+                    .position(|(tl, br)| tl.x == start && br.x == end)
+                {
+                    Some(i) => {
+                        let (tl, br) = open.swap_remove(i);
+                        still_open.push((tl, Point::new(br.x, y)));
+                    }
+                    None => still_open.push((Point::new(start, y), Point::new(end, y))),
+                }
+            }
+
+            // anything not carried on to this row is finished
+            rectangles.append(&mut open);
+            open = still_open;
+        }
+
+        rectangles.append(&mut open);
+        rectangles.sort_by_key(|(tl, _)| (tl.y, tl.x));
+        rectangles
+    }
+
+    pub fn uncovered_cells(&self, (min, max): (Point, Point)) -> impl Iterator<Item = Point> + '_ {
+        (min.y..=max.y).flat_map(move |y| {
+            self.uncovered(y, min.x..=max.x)
+                .into_iter()
+                .flat_map(move |xs| xs.map(move |x| Point::new(x, y)))
+        })
     }
 
-    fn solve2(&self) -> i64 {
+    fn solve2(&self) -> Option<i64> {
         for sbp in self.input.iter() {
-            let mut points = sbp.perimeter_points().collect::<Vec<_>>();
+            let mut points = sbp
+                .perimeter_points()
+                .filter(|p| self.settings.in_region(p))
+                .collect::<Vec<_>>();
 
             for other in self.input.iter() {
                 if std::ptr::eq(sbp, other) {
@@ -140,19 +297,217 @@ impl Puzzle {
             }
 
             if points.len() == 1 {
-                return points[0].x * 4_000_000 + points[0].y;
+                return Some(self.settings.tuning_frequency(&points[0]));
             }
         }
-        unreachable!()
+        None
     }
+
+    // in coordinates rotated by 45° each range is a square, so the beacon
+    // sits where the one cell gaps between ranges cross, or against the
+    // region's edges
+    fn solve2_rotated(&self) -> Option<i64> {
+        self.distress_beacon()
+            .map(|p| self.settings.tuning_frequency(&p))
+    }
+
+    pub fn distress_beacon(&self) -> Option<Point> {
+        let mut us = Vec::new();
+        let mut vs = Vec::new();
+
+        for (i, a) in self.input.iter().enumerate() {
+            for b in self.input.iter().skip(i + 1) {
+                let (ra, rb) = (a.manhattan_distance(), b.manhattan_distance());
+                let ((au, av), (bu, bv)) = (rotate(&a.0), rotate(&b.0));
+
+                // the gap runs along the edge of a's square facing b
+                if (bu - au).abs() == ra + rb + 2 {
+                    us.push(au + (ra + 1) * (bu - au).signum());
+                }
+                if (bv - av).abs() == ra + rb + 2 {
+                    vs.push(av + (ra + 1) * (bv - av).signum());
+                }
+            }
+        }
+
+        us.sort();
+        us.dedup();
+        vs.sort();
+        vs.dedup();
+
+        let crossings = us
+            .iter()
+            .flat_map(|&u| vs.iter().map(move |&v| (u, v)))
+            // only crossings on whole cells
+            .filter(|(u, v)| (u + v) % 2 == 0)
+            .map(|(u, v)| Point::new((u + v) / 2, (u - v) / 2));
+
+        crossings.chain(self.edge_candidates()).find(|p| {
+            self.settings.in_region(p) && !self.input.iter().any(|sbp| sbp.can_exclude(p))
+        })
+    }
+
+    // A lone cell against the edge of the region needn't sit between two
+    // sensors, only on the outline of one, so also try where each outline
+    // crosses the region's edges, and the region's corners.
+    fn edge_candidates(&self) -> Vec<Point> {
+        let (min, max) = self.settings.region;
+        let mut points = vec![min, max, Point::new(min.x, max.y), Point::new(max.x, min.y)];
+
+        for sbp in self.input.iter() {
+            let (u, v) = rotate(&sbp.0);
+            let r = sbp.manhattan_distance() + 1;
+
+            // x + y = u and x - y = v along the outline's four sides
+            for u in [u - r, u + r] {
+                points.extend([min.x, max.x].map(|x| Point::new(x, u - x)));
+                points.extend([min.y, max.y].map(|y| Point::new(u - y, y)));
+            }
+            for v in [v - r, v + r] {
+                points.extend([min.x, max.x].map(|x| Point::new(x, x - v)));
+                points.extend([min.y, max.y].map(|y| Point::new(v + y, y)));
+            }
+        }
+
+        points
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mark {
+    Uncovered,
+    Covered,
+    Beacon,
+    Sensor,
+    Distress,
+}
+
+impl Mark {
+    fn char(self) -> char {
+        match self {
+            Self::Uncovered => '.',
+            Self::Covered => '#',
+            Self::Beacon => 'B',
+            Self::Sensor => 'S',
+            Self::Distress => 'X',
+        }
+    }
+
+    fn rgb(self) -> [u8; 3] {
+        match self {
+            Self::Uncovered => [0, 0, 0],
+            Self::Covered => [96, 96, 96],
+            Self::Beacon => [64, 128, 255],
+            Self::Sensor => [255, 64, 64],
+            Self::Distress => [255, 255, 0],
+        }
+    }
+}
+
+impl Puzzle {
+    // one mark per `scale` by `scale` block, from its top left cell unless
+    // a sensor, beacon or distress beacon is inside it. The window's corners
+    // can come in any order, and scales below 1 count as 1.
+    fn coverage_map(&self, (a, b): (Point, Point), scale: i64) -> Grid<Mark> {
+        let scale = scale.max(1);
+        let min = Point::new(a.x.min(b.x), a.y.min(b.y));
+        let max = Point::new(a.x.max(b.x), a.y.max(b.y));
+
+        let blocks = |from: i64, to: i64| ((to - from) / scale + 1) as usize;
+        let (width, height) = (blocks(min.x, max.x), blocks(min.y, max.y));
+
+        let cells = (0..height as i64)
+            .flat_map(|by| (0..width as i64).map(move |bx| (bx, by)))
+            .map(|(bx, by)| {
+                let p = Point::new(min.x + bx * scale, min.y + by * scale);
+                if self.input.iter().any(|sbp| sbp.can_exclude(&p)) {
+                    Mark::Covered
+                } else {
+                    Mark::Uncovered
+                }
+            })
+            .collect();
+        let mut map = Grid::from_vec(width, cells);
+
+        let block_of = |p: &Point| {
+            let in_window = (min.x..=max.x).contains(&p.x) && (min.y..=max.y).contains(&p.y);
+            in_window.then(|| {
+                (
+                    ((p.x - min.x) / scale) as usize,
+                    ((p.y - min.y) / scale) as usize,
+                )
+            })
+        };
+
+        let marks = self
+            .input
+            .iter()
+            .map(|SensorBeaconPair(_, beacon)| (*beacon, Mark::Beacon))
+            .chain(
+                self.input
+                    .iter()
+                    .map(|SensorBeaconPair(sensor, _)| (*sensor, Mark::Sensor)),
+            )
+            .chain(self.distress_beacon().map(|p| (p, Mark::Distress)));
+
+        for (p, mark) in marks {
+            if let Some(block) = block_of(&p) {
+                map[block] = mark;
+            }
+        }
+
+        map
+    }
+
+    // like the puzzle's diagrams, with 'X' for the distress beacon
+    pub fn render(&self, window: (Point, Point), scale: i64) -> String {
+        self.coverage_map(window, scale).render(|mark| mark.char())
+    }
+
+    pub fn render_ppm(&self, window: (Point, Point), scale: i64) -> Vec<u8> {
+        let map = self.coverage_map(window, scale);
+
+        let mut image = format!("P6\n{} {}\n255\n", map.width(), map.height()).into_bytes();
+        image.extend(map.cells().iter().flat_map(|mark| mark.rgb()));
+        image
+    }
+}
+
+// (x + y, x - y), which turns diamonds into squares
+fn rotate(p: &Point) -> (i64, i64) {
+    (p.x + p.y, p.x - p.y)
 }
 
 #[aoc_generator(day15)]
-pub fn input_generator(input: &str) -> Puzzle {
-    Puzzle {
-        input: SensorBeaconList::parse(input).unwrap().1,
-        target_y: 2_000_000,
+pub fn input_generator(input: &str) -> Result<Puzzle, ParseError> {
+    // any lines before the first sensor are settings
+    let header_len = input
+        .split_inclusive('\n')
+        .take_while(|line| !line.starts_with("Sensor"))
+        .map(str::len)
+        .sum();
+    let (header, sensors) = input.split_at(header_len);
+
+    let mut settings = Settings::default();
+    for setting in error::parse_lines(
+        header,
+        Setting::parse,
+        "'row y=<y>', 'region x=<min>..=<max>, y=<min>..=<max>' or 'tuning x*<n>+y'",
+    )? {
+        setting.apply(&mut settings);
     }
+
+    let input = error::parse_lines(
+        sensors,
+        SensorBeaconPair::parse,
+        "'Sensor at x=<x>, y=<y>: closest beacon is at x=<x>, y=<y>'",
+    )
+    .map_err(|e| e.shift_lines(header.lines().count()))?;
+
+    Ok(Puzzle {
+        input: SensorBeaconList(input),
+        settings,
+    })
 }
 
 #[aoc(day15, part1)]
@@ -161,15 +516,22 @@ pub fn solve_part1(input: &Puzzle) -> usize {
 }
 
 #[aoc(day15, part2)]
-pub fn solve_part2(input: &Puzzle) -> i64 {
-    input.solve2()
+pub fn solve_part2(input: &Puzzle) -> Result<i64, NoDistressBeacon> {
+    input.solve2().ok_or(NoDistressBeacon)
+}
+
+#[aoc(day15, part2, Rotated)]
+pub fn solve_part2_rotated(input: &Puzzle) -> Result<i64, NoDistressBeacon> {
+    input.solve2_rotated().ok_or(NoDistressBeacon)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_INPUT: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+    const EXAMPLE_INPUT: &str = "row y=10
+region x=0..=20, y=0..=20
+Sensor at x=2, y=18: closest beacon is at x=-2, y=15
 Sensor at x=9, y=16: closest beacon is at x=10, y=16
 Sensor at x=13, y=2: closest beacon is at x=15, y=3
 Sensor at x=12, y=14: closest beacon is at x=10, y=16
@@ -187,7 +549,37 @@ Sensor at x=20, y=1: closest beacon is at x=15, y=3";
     #[test]
     fn known_at() {
         let sbp = SensorBeaconPair(Point { x: 8, y: 7 }, Point { x: 2, y: 10 });
-        assert_eq!(sbp.known_at(10), 2..=14);
+        assert_eq!(sbp.known_at(10), Some(2..=14));
+        assert_eq!(sbp.known_at(16), Some(8..=8));
+        assert_eq!(sbp.known_at(17), None);
+    }
+
+    #[test]
+    fn covered_with_gaps() {
+        // the third sensor doesn't reach row 0
+        let puzzle = input_generator(
+            "row y=0
+Sensor at x=0, y=0: closest beacon is at x=2, y=0
+Sensor at x=10, y=0: closest beacon is at x=11, y=0
+Sensor at x=0, y=10: closest beacon is at x=0, y=11",
+        )
+        .unwrap();
+
+        assert_eq!(puzzle.covered(0), vec![-2..=2, 9..=11]);
+        assert_eq!(puzzle.uncovered(0, -5..=15), vec![-5..=-3, 3..=8, 12..=15]);
+        assert_eq!(puzzle.uncovered(0, 0..=10), vec![3..=8]);
+        assert_eq!(puzzle.uncovered(0, 0..=1), vec![]);
+
+        assert_eq!(solve_part1(&puzzle), 6);
+    }
+
+    #[test]
+    fn covered_example() {
+        let puzzle = input_generator(EXAMPLE_INPUT).unwrap();
+
+        assert_eq!(puzzle.covered(10), vec![-2..=24]);
+        assert_eq!(puzzle.uncovered(11, 0..=20), vec![14..=14]);
+        assert_eq!(puzzle.uncovered(10, 0..=20), vec![]);
     }
 
     #[test]
@@ -214,14 +606,200 @@ Sensor at x=20, y=1: closest beacon is at x=15, y=3";
 
     #[test]
     fn examples_part1() {
-        let mut puzzle = input_generator(EXAMPLE_INPUT);
-        puzzle.target_y = 10;
+        let puzzle = input_generator(EXAMPLE_INPUT).unwrap();
         assert_eq!(solve_part1(&puzzle), 26);
     }
 
     #[test]
     fn examples_part2() {
-        let puzzle = input_generator(EXAMPLE_INPUT);
-        assert_eq!(solve_part2(&puzzle), 56000011);
+        let puzzle = input_generator(EXAMPLE_INPUT).unwrap();
+        assert_eq!(solve_part2(&puzzle), Ok(56000011));
+        assert_eq!(solve_part2_rotated(&puzzle), Ok(56000011));
+    }
+
+    #[test]
+    fn uncovered_region() {
+        let puzzle = input_generator(EXAMPLE_INPUT).unwrap();
+        let region = puzzle.settings().region;
+        let beacon = Point::new(14, 11);
+
+        assert_eq!(puzzle.uncovered_region(region), vec![(beacon, beacon)]);
+        assert_eq!(
+            puzzle.uncovered_cells(region).collect::<Vec<_>>(),
+            vec![beacon]
+        );
+
+        //    -1 0 1 2 3
+        // -1  . # . . .
+        //  0  # S # . .
+        //  1  . # . . .
+        let puzzle = input_generator("Sensor at x=0, y=0: closest beacon is at x=1, y=0").unwrap();
+        let region = (Point::new(-1, -1), Point::new(3, 1));
+        let point = |x, y| Point::new(x, y);
+
+        assert_eq!(
+            puzzle.uncovered_region(region),
+            vec![
+                (point(-1, -1), point(-1, -1)),
+                (point(1, -1), point(3, -1)),
+                (point(2, 0), point(3, 0)),
+                (point(-1, 1), point(-1, 1)),
+                (point(1, 1), point(3, 1)),
+            ]
+        );
+        assert_eq!(
+            puzzle.uncovered_region((point(2, -1), point(3, 1))),
+            vec![(point(2, -1), point(3, 1))]
+        );
+
+        let cells = puzzle.uncovered_cells(region).collect::<Vec<_>>();
+        assert_eq!(cells.len(), 10);
+        assert_eq!(cells[..3], [point(-1, -1), point(1, -1), point(2, -1)]);
+    }
+
+    #[test]
+    fn distress_beacon_on_the_edge() {
+        // the sensors on either side of (2, 0) overlap, so it isn't in the
+        // gap between any two of them
+        let puzzle = input_generator(
+            "region x=0..=4, y=0..=2
+Sensor at x=0, y=1: closest beacon is at x=0, y=3
+Sensor at x=4, y=1: closest beacon is at x=4, y=3
+Sensor at x=2, y=4: closest beacon is at x=2, y=6",
+        )
+        .unwrap();
+
+        let region = puzzle.settings().region;
+        assert_eq!(
+            puzzle.uncovered_cells(region).collect::<Vec<_>>(),
+            vec![Point::new(2, 0)]
+        );
+        assert_eq!(puzzle.distress_beacon(), Some(Point::new(2, 0)));
+        assert_eq!(puzzle.solve2(), puzzle.solve2_rotated());
+
+        // in the corner, with a single sensor
+        let puzzle = input_generator(
+            "region x=0..=2, y=0..=2
+Sensor at x=2, y=2: closest beacon is at x=2, y=-1",
+        )
+        .unwrap();
+
+        assert_eq!(puzzle.distress_beacon(), Some(Point::new(0, 0)));
+        assert_eq!(puzzle.solve2(), Some(0));
+        assert_eq!(puzzle.solve2_rotated(), Some(0));
+    }
+
+    #[test]
+    fn no_distress_beacon() {
+        let puzzle = input_generator(
+            "region x=-1..=1, y=-1..=1\nSensor at x=0, y=0: closest beacon is at x=2, y=0",
+        )
+        .unwrap();
+
+        assert_eq!(puzzle.uncovered_region(puzzle.settings().region), vec![]);
+        assert_eq!(solve_part2(&puzzle), Err(NoDistressBeacon));
+        assert_eq!(solve_part2_rotated(&puzzle), Err(NoDistressBeacon));
+    }
+
+    #[test]
+    fn render() {
+        let puzzle = input_generator(EXAMPLE_INPUT).unwrap();
+        let window = (Point::new(-4, 9), Point::new(26, 11));
+
+        assert_eq!(
+            puzzle.render(window, 1),
+            "...#########################...
+..####B######################..
+.###S#############X###########."
+        );
+
+        // the first diagram in the puzzle, with the coverage filled in
+        let map = puzzle.render((Point::new(-2, 0), Point::new(25, 22)), 1);
+        let lines = map.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 23);
+        assert!(lines.iter().all(|line| line.len() == 28));
+        assert_eq!(map.matches('S').count(), 14);
+        assert_eq!(map.matches('B').count(), 6);
+        assert_eq!(lines[16], "###########SB###############");
+
+        // scaled down, sensors and the distress beacon still show
+        let map = puzzle.render((Point::new(0, 0), Point::new(19, 19)), 10);
+        assert_eq!(map, "SS\nSX");
+        let map = puzzle.render((Point::new(0, 0), Point::new(29, 29)), 15);
+        assert_eq!(map.lines().count(), 2);
+
+        // swapped corners and a scale below 1 still draw the window
+        assert_eq!(
+            puzzle.render((window.1, window.0), 0),
+            puzzle.render(window, 1)
+        );
+        assert_eq!(
+            puzzle.render((Point::new(26, 9), Point::new(-4, 11)), -3),
+            puzzle.render(window, 1)
+        );
+    }
+
+    #[test]
+    fn render_ppm() {
+        let puzzle = input_generator(EXAMPLE_INPUT).unwrap();
+        let image = puzzle.render_ppm((Point::new(0, 0), Point::new(20, 20)), 5);
+
+        let header = b"P6\n5 5\n255\n";
+        assert_eq!(&image[..header.len()], header);
+        assert_eq!(image.len(), header.len() + 5 * 5 * 3);
+    }
+
+    #[test]
+    fn settings() {
+        let sensors = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15";
+
+        assert_eq!(
+            input_generator(sensors).unwrap().settings(),
+            &Settings::default()
+        );
+
+        let puzzle = input_generator(&format!(
+            "tuning x*10+y\nregion x=-5..=5, y=1..=2\nrow y=3\n{sensors}"
+        ))
+        .unwrap();
+        assert_eq!(
+            puzzle.settings(),
+            &Settings {
+                target_y: 3,
+                region: (Point::new(-5, 1), Point::new(5, 2)),
+                tuning: 10,
+            }
+        );
+        assert_eq!(puzzle.settings().tuning_frequency(&Point::new(4, 2)), 42);
+        assert!(puzzle.settings().in_region(&Point::new(-5, 2)));
+        assert!(!puzzle.settings().in_region(&Point::new(0, 0)));
+    }
+
+    #[test]
+    fn invalid_settings() {
+        assert_eq!(
+            input_generator(
+                "row y=10\nrow x=3\nSensor at x=2, y=18: closest beacon is at x=-2, y=15"
+            )
+            .err()
+            .map(|e| (e.line, e.column, e.text)),
+            Some((2, 1, "row x=3".to_string()))
+        );
+        assert_eq!(
+            input_generator("row y=10\nSensor at x=2, y=18: closest beacon is at x=-2, y=1b")
+                .err()
+                .map(|e| (e.line, e.column, e.text)),
+            Some((2, 52, "b".to_string()))
+        );
+    }
+
+    #[test]
+    fn invalid_sensor() {
+        assert_eq!(
+            input_generator("Sensor at x=2, y=18: closest beacon is at x=-2, y=15\nSensor at x=9, y=1b: closest beacon is at x=10, y=16")
+                .err()
+                .map(|e| (e.line, e.column, e.text)),
+            Some((2, 19, "b: closest beacon is at x=10, y=16".to_string()))
+        );
     }
 }
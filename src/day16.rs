@@ -0,0 +1,302 @@
+use std::collections::{HashMap, VecDeque};
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alpha1, u32},
+    multi::separated_list1,
+    sequence::{preceded, tuple},
+    IResult,
+};
+
+use crate::error::{self, ParseError};
+
+const START: &str = "AA";
+
+// opened valves are tracked as bits of a usize, with a table entry for
+// every combination of them
+const MAX_USEFUL: usize = 20;
+
+struct Valve<'a> {
+    name: &'a str,
+    rate: u32,
+    tunnels: Vec<&'a str>,
+}
+
+// the valve graph compressed down to the valves worth opening
+#[derive(Debug)]
+pub struct Network {
+    // flow rate of each useful valve
+    rates: Vec<u32>,
+    // dist[i][j] = minutes to walk from useful valve i to useful valve j
+    dist: Vec<Vec<u32>>,
+    // minutes to walk from the start to each useful valve
+    from_start: Vec<u32>,
+}
+
+impl<'a> Valve<'a> {
+    fn parse(input: &'a str) -> IResult<&'a str, Self> {
+        let (rest, (name, rate, tunnels)) = tuple((
+            preceded(tag("Valve "), alpha1),
+            preceded(tag(" has flow rate="), u32),
+            preceded(
+                alt((
+                    tag("; tunnels lead to valves "),
+                    tag("; tunnel leads to valve "),
+                )),
+                separated_list1(tag(", "), alpha1),
+            ),
+        ))(input)?;
+
+        Ok((
+            rest,
+            Self {
+                name,
+                rate,
+                tunnels,
+            },
+        ))
+    }
+}
+
+impl Network {
+    fn new(valves: &[Valve]) -> Self {
+        let index: HashMap<_, _> = valves
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (v.name, i))
+            .collect();
+        let adjacency: Vec<Vec<usize>> = valves
+            .iter()
+            .map(|v| v.tunnels.iter().map(|t| index[t]).collect())
+            .collect();
+
+        // minutes from `from` to every valve, walking the full graph
+        let distances = |from: usize| {
+            let mut dist = vec![u32::MAX; valves.len()];
+            let mut queue = VecDeque::from([from]);
+            dist[from] = 0;
+
+            while let Some(cur) = queue.pop_front() {
+                for &next in &adjacency[cur] {
+                    if dist[next] == u32::MAX {
+                        dist[next] = dist[cur] + 1;
+                        queue.push_back(next);
+                    }
+                }
+            }
+
+            dist
+        };
+
+        let useful: Vec<_> = (0..valves.len()).filter(|&i| valves[i].rate > 0).collect();
+        let pick = |dist: Vec<u32>| useful.iter().map(|&j| dist[j]).collect::<Vec<_>>();
+
+        Self {
+            rates: useful.iter().map(|&i| valves[i].rate).collect(),
+            dist: useful.iter().map(|&i| pick(distances(i))).collect(),
+            from_start: pick(distances(index[START])),
+        }
+    }
+
+    // records in `best` the most pressure for each set of opened valves
+    fn explore(&self, dist: &[u32], time: u32, opened: usize, pressure: u32, best: &mut [u32]) {
+        best[opened] = best[opened].max(pressure);
+
+        for (next, &walk) in dist.iter().enumerate() {
+            if opened & (1 << next) != 0 {
+                continue;
+            }
+
+            // walk there and spend a minute opening it
+            let cost = walk.saturating_add(1);
+            if cost >= time {
+                continue;
+            }
+
+            let time = time - cost;
+            self.explore(
+                &self.dist[next],
+                time,
+                opened | (1 << next),
+                pressure + time * self.rates[next],
+                best,
+            );
+        }
+    }
+
+    fn best_by_opened(&self, time: u32) -> Vec<u32> {
+        let mut best = vec![0; 1 << self.rates.len()];
+        self.explore(&self.from_start, time, 0, 0, &mut best);
+        best
+    }
+
+    fn solve(&self) -> u32 {
+        self.best_by_opened(30).into_iter().max().unwrap()
+    }
+
+    fn solve_part2(&self) -> u32 {
+        let n = self.rates.len();
+        let best = self.best_by_opened(26);
+
+        // within_subset[mask] = most pressure from opening only valves in mask
+        let mut within_subset = best.clone();
+        for bit in 0..n {
+            for mask in 0..within_subset.len() {
+                if mask & (1 << bit) != 0 {
+                    within_subset[mask] = within_subset[mask].max(within_subset[mask ^ (1 << bit)]);
+                }
+            }
+        }
+
+        // the elephant takes the best it can from whatever we leave
+        let all = (1 << n) - 1;
+        best.iter()
+            .enumerate()
+            .map(|(mask, &ours)| ours + within_subset[all & !mask])
+            .max()
+            .unwrap()
+    }
+}
+
+#[aoc_generator(day16)]
+pub fn input_generator(input: &str) -> Result<Network, ParseError> {
+    let valves = error::parse_lines(
+        input,
+        Valve::parse,
+        "'Valve <name> has flow rate=<rate>; tunnels lead to valves <names>'",
+    )?;
+
+    let mut useful = 0;
+    for (i, (valve, line)) in valves.iter().zip(input.lines()).enumerate() {
+        if valves[..i].iter().any(|v| v.name == valve.name) {
+            return Err(
+                ParseError::at(line, valve.name, "valve name not used before").shift_lines(i),
+            );
+        }
+
+        if valve.rate > 0 {
+            useful += 1;
+            if useful > MAX_USEFUL {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    format!("at most {MAX_USEFUL} valves with a positive flow rate"),
+                ));
+            }
+        }
+    }
+
+    for (i, (valve, line)) in valves.iter().zip(input.lines()).enumerate() {
+        for tunnel in &valve.tunnels {
+            if !valves.iter().any(|v| v.name == *tunnel) {
+                return Err(ParseError::at(line, tunnel, "name of a known valve").shift_lines(i));
+            }
+        }
+    }
+
+    if !valves.iter().any(|v| v.name == START) {
+        return Err(ParseError::at(input, &input[input.len()..], "valve 'AA'"));
+    }
+
+    Ok(Network::new(&valves))
+}
+
+#[aoc(day16, part1)]
+pub fn solve_part1(input: &Network) -> u32 {
+    input.solve()
+}
+
+#[aoc(day16, part2)]
+pub fn solve_part2(input: &Network) -> u32 {
+    input.solve_part2()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II";
+
+    #[test]
+    fn compressed_network() {
+        let network = input_generator(EXAMPLE_INPUT).unwrap();
+
+        // BB, CC, DD, EE, HH, JJ
+        assert_eq!(network.rates, vec![13, 2, 20, 3, 22, 21]);
+        assert_eq!(network.from_start, vec![1, 2, 1, 2, 5, 2]);
+        assert_eq!(network.dist[4][5], 7);
+    }
+
+    #[test]
+    fn examples_part1() {
+        assert_eq!(solve_part1(&input_generator(EXAMPLE_INPUT).unwrap()), 1651);
+    }
+
+    #[test]
+    fn examples_part2() {
+        assert_eq!(solve_part2(&input_generator(EXAMPLE_INPUT).unwrap()), 1707);
+    }
+
+    #[test]
+    fn unknown_tunnel() {
+        assert_eq!(
+            input_generator("Valve AA has flow rate=0; tunnel leads to valve ZZ").err(),
+            Some(ParseError::new(1, 49, "ZZ", "name of a known valve"))
+        );
+    }
+
+    #[test]
+    fn duplicate_valve() {
+        assert_eq!(
+            input_generator(
+                "Valve AA has flow rate=0; tunnel leads to valve BB\nValve BB has flow rate=1; tunnel leads to valve AA\nValve BB has flow rate=2; tunnel leads to valve AA"
+            )
+            .err(),
+            Some(ParseError::new(
+                3,
+                7,
+                "BB has flow rate=2; tunnel leads to valve AA",
+                "valve name not used before"
+            ))
+        );
+    }
+
+    #[test]
+    fn too_many_valves() {
+        // AA leading to `n` useful valves
+        let network = |n: usize| {
+            let names = (0..n)
+                .map(|i| format!("V{}", (b'A' + i as u8) as char))
+                .collect::<Vec<_>>();
+            let mut input = format!(
+                "Valve AA has flow rate=0; tunnels lead to valves {}",
+                names.join(", ")
+            );
+            for name in &names {
+                input.push_str(&format!(
+                    "\nValve {name} has flow rate=1; tunnel leads to valve AA"
+                ));
+            }
+            input_generator(&input)
+        };
+
+        assert!(network(MAX_USEFUL).is_ok());
+
+        let error = network(MAX_USEFUL + 1).err().unwrap();
+        assert_eq!(error.line, MAX_USEFUL + 2);
+        assert_eq!(
+            error.expected,
+            "at most 20 valves with a positive flow rate"
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+
+use crate::error::ParseError;
+
+const WIDTH: usize = 7;
+
+// Rocks as rows from the bottom up, one bit per column with the leftmost
+// column in the highest bit, already positioned two units from the left
+// wall. Unused rows are zero.
+const ROCKS: [[u8; 4]; 5] = [
+    [0b0011110, 0, 0, 0],
+    [0b0001000, 0b0011100, 0b0001000, 0],
+    [0b0011100, 0b0000100, 0b0000100, 0],
+    [0b0010000, 0b0010000, 0b0010000, 0b0010000],
+    [0b0011000, 0b0011000, 0, 0],
+];
+
+const LEFT_WALL: u8 = 1 << (WIDTH - 1);
+const RIGHT_WALL: u8 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Jet {
+    Left,
+    Right,
+}
+
+struct Chamber<'a> {
+    jets: &'a [Jet],
+    next_jet: usize,
+    next_rock: usize,
+    rows: Vec<u8>,
+}
+
+// What the top of the chamber looks like before the next rock falls: the
+// next rock and jet, and how far down each column's highest block is.
+type Fingerprint = (usize, usize, [usize; WIDTH]);
+
+impl TryFrom<char> for Jet {
+    type Error = char;
+
+    fn try_from(ch: char) -> Result<Self, Self::Error> {
+        match ch {
+            '<' => Ok(Self::Left),
+            '>' => Ok(Self::Right),
+            other => Err(other),
+        }
+    }
+}
+
+impl<'a> Chamber<'a> {
+    fn new(jets: &'a [Jet]) -> Self {
+        Self {
+            jets,
+            next_jet: 0,
+            next_rock: 0,
+            rows: Vec::new(),
+        }
+    }
+
+    fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn collides(&self, rock: &[u8; 4], y: usize) -> bool {
+        rock.iter()
+            .enumerate()
+            .any(|(i, &r)| r & self.rows.get(y + i).copied().unwrap_or(0) != 0)
+    }
+
+    fn push(rock: &[u8; 4], jet: Jet) -> Option<[u8; 4]> {
+        match jet {
+            Jet::Left if rock.iter().all(|r| r & LEFT_WALL == 0) => Some(rock.map(|r| r << 1)),
+            Jet::Right if rock.iter().all(|r| r & RIGHT_WALL == 0) => Some(rock.map(|r| r >> 1)),
+            _ => None,
+        }
+    }
+
+    fn drop_rock(&mut self) {
+        let mut rock = ROCKS[self.next_rock];
+        self.next_rock = (self.next_rock + 1) % ROCKS.len();
+
+        // y is the row of the rock's bottom edge
+        let mut y = self.height() + 3;
+
+        loop {
+            let jet = self.jets[self.next_jet];
+            self.next_jet = (self.next_jet + 1) % self.jets.len();
+
+            if let Some(pushed) = Self::push(&rock, jet) {
+                if !self.collides(&pushed, y) {
+                    rock = pushed;
+                }
+            }
+
+            if y == 0 || self.collides(&rock, y - 1) {
+                break;
+            }
+
+            y -= 1;
+        }
+
+        for (i, &row) in rock.iter().enumerate().filter(|(_, &r)| r != 0) {
+            if y + i >= self.rows.len() {
+                self.rows.resize(y + i + 1, 0);
+            }
+
+            self.rows[y + i] |= row;
+        }
+    }
+
+    fn fingerprint(&self) -> Fingerprint {
+        let mut depths = [0; WIDTH];
+
+        for (col, depth) in depths.iter_mut().enumerate() {
+            let bit = LEFT_WALL >> col;
+            *depth = self
+                .rows
+                .iter()
+                .rev()
+                .position(|r| r & bit != 0)
+                .unwrap_or(self.rows.len());
+        }
+
+        (self.next_rock, self.next_jet, depths)
+    }
+}
+
+fn height_after(jets: &[Jet], rocks: usize) -> usize {
+    let mut chamber = Chamber::new(jets);
+
+    for _ in 0..rocks {
+        chamber.drop_rock();
+    }
+
+    chamber.height()
+}
+
+// skips ahead by whole cycles once the top of the chamber repeats
+fn height_after_cycles(jets: &[Jet], rocks: usize) -> usize {
+    let mut chamber = Chamber::new(jets);
+    let mut seen: HashMap<Fingerprint, (usize, usize)> = HashMap::new();
+
+    for dropped in 1..=rocks {
+        chamber.drop_rock();
+
+        let height = chamber.height();
+        if let Some((prev_dropped, prev_height)) =
+            seen.insert(chamber.fingerprint(), (dropped, height))
+        {
+            let cycle_len = dropped - prev_dropped;
+            let cycles = (rocks - dropped) / cycle_len;
+
+            for _ in 0..(rocks - dropped) % cycle_len {
+                chamber.drop_rock();
+            }
+
+            return chamber.height() + cycles * (height - prev_height);
+        }
+    }
+
+    chamber.height()
+}
+
+#[aoc_generator(day17)]
+pub fn input_generator(input: &str) -> Result<Vec<Jet>, ParseError> {
+    let input = input.trim_end();
+
+    if input.is_empty() {
+        return Err(ParseError::new(1, 1, "", "jet pattern of '<' and '>'"));
+    }
+
+    input
+        .char_indices()
+        .map(|(i, ch)| {
+            Jet::try_from(ch).map_err(|_| ParseError::at(input, &input[i..], "'<' or '>'"))
+        })
+        .collect()
+}
+
+#[aoc(day17, part1)]
+pub fn solve_part1(input: &[Jet]) -> usize {
+    height_after(input, 2022)
+}
+
+#[aoc(day17, part2)]
+pub fn solve_part2(input: &[Jet]) -> usize {
+    height_after_cycles(input, 1_000_000_000_000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+
+    #[test]
+    fn first_rocks() {
+        let jets = input_generator(EXAMPLE_INPUT).unwrap();
+        assert_eq!(height_after(&jets, 1), 1);
+        assert_eq!(height_after(&jets, 2), 4);
+        assert_eq!(height_after(&jets, 3), 6);
+        assert_eq!(height_after(&jets, 10), 17);
+    }
+
+    #[test]
+    fn cycles_match_simulation() {
+        let jets = input_generator(EXAMPLE_INPUT).unwrap();
+        for rocks in [100, 2022, 3000] {
+            assert_eq!(
+                height_after_cycles(&jets, rocks),
+                height_after(&jets, rocks)
+            );
+        }
+    }
+
+    #[test]
+    fn examples_part1() {
+        assert_eq!(solve_part1(&input_generator(EXAMPLE_INPUT).unwrap()), 3068);
+    }
+
+    #[test]
+    fn examples_part2() {
+        assert_eq!(
+            solve_part2(&input_generator(EXAMPLE_INPUT).unwrap()),
+            1514285714288
+        );
+    }
+
+    #[test]
+    fn invalid_jet() {
+        assert_eq!(
+            input_generator("<<>^>").err(),
+            Some(ParseError::new(1, 4, "^>", "'<' or '>'"))
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::{HashSet, VecDeque};
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::i32,
+    combinator::map,
+    sequence::{terminated, tuple},
+    IResult,
+};
+
+use crate::error::{self, ParseError};
+use crate::point::Point3;
+
+type Cube = Point3<i32>;
+
+#[derive(Debug)]
+pub struct Droplet {
+    cubes: HashSet<Cube>,
+}
+
+fn parse_cube(input: &str) -> IResult<&str, Cube> {
+    map(
+        tuple((terminated(i32, tag(",")), terminated(i32, tag(",")), i32)),
+        |(x, y, z)| Cube::new(x, y, z),
+    )(input)
+}
+
+impl Droplet {
+    // including faces of air pockets trapped inside
+    fn surface_area(&self) -> usize {
+        self.cubes
+            .iter()
+            .flat_map(|c| c.neighbors6())
+            .filter(|n| !self.cubes.contains(n))
+            .count()
+    }
+
+    // only faces steam can reach from outside
+    fn exterior_surface_area(&self) -> usize {
+        let Some((min, max)) = Cube::bounds(self.cubes.iter().copied()) else {
+            return 0;
+        };
+
+        // leave a layer of air around the droplet so steam can get everywhere
+        let min = min - Cube::new(1, 1, 1);
+        let max = max + Cube::new(1, 1, 1);
+        let in_bounds = |p: &Cube| {
+            (min.x..=max.x).contains(&p.x)
+                && (min.y..=max.y).contains(&p.y)
+                && (min.z..=max.z).contains(&p.z)
+        };
+
+        let mut steam = HashSet::from([min]);
+        let mut queue = VecDeque::from([min]);
+        let mut faces = 0;
+
+        while let Some(cur) = queue.pop_front() {
+            for next in cur.neighbors6() {
+                if self.cubes.contains(&next) {
+                    faces += 1;
+                } else if in_bounds(&next) && steam.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        faces
+    }
+}
+
+#[aoc_generator(day18)]
+pub fn input_generator(input: &str) -> Result<Droplet, ParseError> {
+    let cubes = error::parse_lines(input, parse_cube, "'<x>,<y>,<z>'")?;
+
+    Ok(Droplet {
+        cubes: cubes.into_iter().collect(),
+    })
+}
+
+#[aoc(day18, part1)]
+pub fn solve_part1(input: &Droplet) -> usize {
+    input.surface_area()
+}
+
+#[aoc(day18, part2)]
+pub fn solve_part2(input: &Droplet) -> usize {
+    input.exterior_surface_area()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "2,2,2
+1,2,2
+3,2,2
+2,1,2
+2,3,2
+2,2,1
+2,2,3
+2,2,4
+2,2,6
+1,2,5
+3,2,5
+2,1,5
+2,3,5";
+
+    #[test]
+    fn two_cubes() {
+        let droplet = input_generator("1,1,1\n2,1,1").unwrap();
+        assert_eq!(droplet.surface_area(), 10);
+        assert_eq!(droplet.exterior_surface_area(), 10);
+    }
+
+    #[test]
+    fn examples_part1() {
+        assert_eq!(solve_part1(&input_generator(EXAMPLE_INPUT).unwrap()), 64);
+    }
+
+    #[test]
+    fn examples_part2() {
+        assert_eq!(solve_part2(&input_generator(EXAMPLE_INPUT).unwrap()), 58);
+    }
+
+    #[test]
+    fn invalid_cube() {
+        assert_eq!(
+            input_generator("1,1,1\n2;1,1").err(),
+            Some(ParseError::new(2, 2, ";1,1", "'<x>,<y>,<z>'"))
+        );
+    }
+}
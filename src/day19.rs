@@ -0,0 +1,235 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::u32,
+    combinator::map,
+    sequence::{delimited, preceded, tuple},
+    IResult,
+};
+
+use crate::error::{self, ParseError};
+
+// resources and robots, in the order the blueprints list them (clay is 1)
+const ORE: usize = 0;
+const OBSIDIAN: usize = 2;
+const GEODE: usize = 3;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Blueprint {
+    id: u32,
+    // costs[robot][resource], for ore, clay and obsidian
+    costs: [[u32; 3]; 4],
+    // only one robot can be built per minute, so collecting more of a
+    // resource than the most expensive robot needs is pointless
+    max_useful: [u32; 3],
+}
+
+#[derive(Debug, Clone, Copy)]
+struct State {
+    minutes_left: u32,
+    robots: [u32; 3],
+    stock: [u32; 3],
+    // geodes that will have been cracked by the end, counted up front when
+    // each geode robot is built
+    geodes: u32,
+}
+
+impl Blueprint {
+    fn new(id: u32, costs: [[u32; 3]; 4]) -> Self {
+        let max_useful =
+            std::array::from_fn(|resource| costs.iter().map(|c| c[resource]).max().unwrap());
+
+        Self {
+            id,
+            costs,
+            max_useful,
+        }
+    }
+
+    fn parse(input: &str) -> IResult<&str, Self> {
+        map(
+            tuple((
+                preceded(tag("Blueprint "), u32),
+                preceded(tag(": Each ore robot costs "), u32),
+                preceded(tag(" ore. Each clay robot costs "), u32),
+                preceded(tag(" ore. Each obsidian robot costs "), u32),
+                preceded(tag(" ore and "), u32),
+                preceded(tag(" clay. Each geode robot costs "), u32),
+                delimited(tag(" ore and "), u32, tag(" obsidian.")),
+            )),
+            |(id, ore, clay, obsidian_ore, obsidian_clay, geode_ore, geode_obsidian)| {
+                Self::new(
+                    id,
+                    [
+                        [ore, 0, 0],
+                        [clay, 0, 0],
+                        [obsidian_ore, obsidian_clay, 0],
+                        [geode_ore, 0, geode_obsidian],
+                    ],
+                )
+            },
+        )(input)
+    }
+
+    pub fn max_geodes(&self, minutes: u32) -> u32 {
+        let mut best = 0;
+
+        self.search(
+            State {
+                minutes_left: minutes,
+                robots: [1, 0, 0],
+                stock: [0; 3],
+                geodes: 0,
+            },
+            &mut best,
+        );
+
+        best
+    }
+
+    // `None` if nothing collects one of the resources it needs
+    fn wait_for(&self, robot: usize, state: &State) -> Option<u32> {
+        (ORE..=OBSIDIAN)
+            .map(|resource| {
+                let missing = self.costs[robot][resource].saturating_sub(state.stock[resource]);
+                match (missing, state.robots[resource]) {
+                    (0, _) => Some(0),
+                    (_, 0) => None,
+                    (missing, robots) => Some(missing.div_ceil(robots)),
+                }
+            })
+            .try_fold(0, |acc, wait| Some(acc.max(wait?)))
+    }
+
+    // branches on which robot to build next rather than on each minute
+    fn search(&self, state: State, best: &mut u32) {
+        *best = (*best).max(state.geodes);
+
+        // even a new geode robot every minute from now on can't beat it
+        let t = state.minutes_left;
+        if state.geodes + t * t.saturating_sub(1) / 2 <= *best {
+            return;
+        }
+
+        for robot in (ORE..=GEODE).rev() {
+            if robot != GEODE && state.robots[robot] >= self.max_useful[robot] {
+                continue;
+            }
+
+            let Some(wait) = self.wait_for(robot, &state) else {
+                continue;
+            };
+
+            // it has to be built with at least a minute left to be any use
+            if wait + 1 >= state.minutes_left {
+                continue;
+            }
+
+            let mut next = state;
+            next.minutes_left -= wait + 1;
+
+            for resource in ORE..=OBSIDIAN {
+                next.stock[resource] += state.robots[resource] * (wait + 1);
+                next.stock[resource] -= self.costs[robot][resource];
+            }
+
+            if robot == GEODE {
+                next.geodes += next.minutes_left;
+            } else {
+                next.robots[robot] += 1;
+            }
+
+            self.search(next, best);
+        }
+    }
+}
+
+fn for_each_blueprint<T: Send>(
+    blueprints: &[Blueprint],
+    f: impl Fn(&Blueprint) -> T + Sync,
+) -> Vec<T> {
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = blueprints
+            .iter()
+            .map(|blueprint| scope.spawn(|| f(blueprint)))
+            .collect();
+
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    })
+}
+
+#[aoc_generator(day19)]
+pub fn input_generator(input: &str) -> Result<Vec<Blueprint>, ParseError> {
+    error::parse_lines(
+        input,
+        Blueprint::parse,
+        "'Blueprint <id>: Each ore robot costs <n> ore. ...'",
+    )
+}
+
+#[aoc(day19, part1)]
+pub fn solve_part1(input: &[Blueprint]) -> u32 {
+    for_each_blueprint(input, |b| b.id * b.max_geodes(24))
+        .into_iter()
+        .sum()
+}
+
+#[aoc(day19, part2)]
+pub fn solve_part2(input: &[Blueprint]) -> u32 {
+    let input = &input[..input.len().min(3)];
+
+    for_each_blueprint(input, |b| b.max_geodes(32))
+        .into_iter()
+        .product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // one blueprint per line, as in the real input
+    const EXAMPLE_INPUT: &str = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
+Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.";
+
+    #[test]
+    fn parse() {
+        let blueprints = input_generator(EXAMPLE_INPUT).unwrap();
+        assert_eq!(
+            blueprints[0],
+            Blueprint::new(1, [[4, 0, 0], [2, 0, 0], [3, 14, 0], [2, 0, 7]])
+        );
+        assert_eq!(blueprints[0].max_useful, [4, 14, 7]);
+    }
+
+    #[test]
+    fn max_geodes() {
+        let blueprints = input_generator(EXAMPLE_INPUT).unwrap();
+        assert_eq!(blueprints[0].max_geodes(24), 9);
+        assert_eq!(blueprints[1].max_geodes(24), 12);
+    }
+
+    #[test]
+    fn examples_part1() {
+        assert_eq!(solve_part1(&input_generator(EXAMPLE_INPUT).unwrap()), 33);
+    }
+
+    #[test]
+    fn examples_part2() {
+        assert_eq!(
+            solve_part2(&input_generator(EXAMPLE_INPUT).unwrap()),
+            56 * 62
+        );
+    }
+
+    #[test]
+    fn invalid_blueprint() {
+        assert_eq!(
+            input_generator("Blueprint 1: Each ore robot costs four ore.").err(),
+            Some(ParseError::new(
+                1,
+                35,
+                "four ore.",
+                "'Blueprint <id>: Each ore robot costs <n> ore. ...'"
+            ))
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use crate::error::ParseError;
+
 #[derive(Clone, Copy)]
 enum Choice {
     Rock,
@@ -38,13 +40,15 @@ impl Choice {
     }
 }
 
-impl From<char> for Choice {
-    fn from(ch: char) -> Self {
+impl TryFrom<char> for Choice {
+    type Error = char;
+
+    fn try_from(ch: char) -> Result<Self, Self::Error> {
         match ch {
-            'A' | 'X' => Self::Rock,
-            'B' | 'Y' => Self::Paper,
-            'C' | 'Z' => Self::Scissors,
-            other => panic!("Unknown choice '{}'", other),
+            'A' | 'X' => Ok(Self::Rock),
+            'B' | 'Y' => Ok(Self::Paper),
+            'C' | 'Z' => Ok(Self::Scissors),
+            other => Err(other),
         }
     }
 }
@@ -91,18 +95,41 @@ impl From<Choice> for Outcome {
     }
 }
 
-#[aoc_generator(day2)]
-pub fn input_generator(input: &str) -> Vec<Round> {
-    let mut rounds = Vec::new();
-
-    for bytes in input.as_bytes().chunks(4) {
-        rounds.push(Round {
-            elf: Choice::from(bytes[0] as char),
-            own: Choice::from(bytes[2] as char),
-        })
+impl TryFrom<&str> for Round {
+    type Error = ParseError;
+
+    fn try_from(line: &str) -> Result<Self, Self::Error> {
+        let mut chars = line.chars();
+
+        let elf = chars
+            .next()
+            .and_then(|ch| Choice::try_from(ch).ok())
+            .ok_or_else(|| ParseError::new(1, 1, line, "'A', 'B' or 'C'"))?;
+
+        if chars.next() != Some(' ') {
+            return Err(ParseError::at(line, line.get(1..).unwrap_or(""), "' '"));
+        }
+
+        let own = chars
+            .next()
+            .and_then(|ch| Choice::try_from(ch).ok())
+            .ok_or_else(|| ParseError::at(line, line.get(2..).unwrap_or(""), "'X', 'Y' or 'Z'"))?;
+
+        if chars.next().is_some() {
+            return Err(ParseError::at(line, &line[3..], "end of line"));
+        }
+
+        Ok(Self { elf, own })
     }
+}
 
-    rounds
+#[aoc_generator(day2)]
+pub fn input_generator(input: &str) -> Result<Vec<Round>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| Round::try_from(line).map_err(|e| e.shift_lines(i)))
+        .collect()
 }
 
 #[aoc(day2, part1)]
@@ -122,11 +149,14 @@ mod tests {
     #[test]
     fn examples_part1() {
         assert_eq!(
-            solve_part1(&input_generator(
-                "A Y\n\
+            solve_part1(
+                &input_generator(
+                    "A Y\n\
 		 B X\n\
 		 C Z\n"
-            )),
+                )
+                .unwrap()
+            ),
             15
         );
     }
@@ -134,12 +164,23 @@ mod tests {
     #[test]
     fn examples_part2() {
         assert_eq!(
-            solve_part2(&input_generator(
-                "A Y\n\
+            solve_part2(
+                &input_generator(
+                    "A Y\n\
 		 B X\n\
 		 C Z\n"
-            )),
+                )
+                .unwrap()
+            ),
             12
         );
     }
+
+    #[test]
+    fn invalid_choice() {
+        assert_eq!(
+            input_generator("A Y\nB W").err(),
+            Some(ParseError::new(2, 3, "W", "'X', 'Y' or 'Z'"))
+        );
+    }
 }
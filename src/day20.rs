@@ -0,0 +1,243 @@
+use nom::character::complete::i64;
+
+use crate::error::{self, ParseError};
+
+const DECRYPTION_KEY: i64 = 811_589_153;
+
+// buckets of about sqrt(n) numbers, so a move only shifts two of them,
+// rebuilt every sqrt(n) moves as they drift out of balance
+#[derive(Debug, Clone)]
+pub struct MixingList {
+    // values in their original order; numbers are identified by index here
+    values: Vec<i64>,
+    buckets: Vec<Vec<usize>>,
+    // which bucket each number currently lives in
+    bucket_of: Vec<usize>,
+    bucket_size: usize,
+    moves_since_rebuild: usize,
+}
+
+impl MixingList {
+    pub fn new(values: Vec<i64>) -> Self {
+        let bucket_size = (values.len() as f64).sqrt().ceil().max(1.0) as usize;
+
+        let mut list = Self {
+            bucket_of: vec![0; values.len()],
+            buckets: Vec::new(),
+            bucket_size,
+            moves_since_rebuild: 0,
+            values,
+        };
+
+        list.rebuild((0..list.values.len()).collect());
+        list
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    fn rebuild(&mut self, order: Vec<usize>) {
+        self.buckets = order
+            .chunks(self.bucket_size)
+            .map(<[usize]>::to_vec)
+            .collect();
+
+        for (b, bucket) in self.buckets.iter().enumerate() {
+            for &id in bucket {
+                self.bucket_of[id] = b;
+            }
+        }
+
+        self.moves_since_rebuild = 0;
+    }
+
+    // numbers are identified by their index in the original list
+    pub fn position(&self, id: usize) -> usize {
+        let b = self.bucket_of[id];
+        let before: usize = self.buckets[..b].iter().map(Vec::len).sum();
+        before + self.buckets[b].iter().position(|&x| x == id).unwrap()
+    }
+
+    pub fn move_number(&mut self, id: usize) {
+        if self.len() < 2 {
+            return;
+        }
+
+        let pos = self.position(id);
+        let b = self.bucket_of[id];
+        self.buckets[b].retain(|&x| x != id);
+
+        // the number isn't in the list while it moves, so there are only
+        // len - 1 others to pass
+        let mut target = (pos as i64 + self.values[id]).rem_euclid(self.len() as i64 - 1) as usize;
+
+        let mut b = 0;
+        while b + 1 < self.buckets.len() && target > self.buckets[b].len() {
+            target -= self.buckets[b].len();
+            b += 1;
+        }
+
+        self.buckets[b].insert(target, id);
+        self.bucket_of[id] = b;
+
+        self.moves_since_rebuild += 1;
+        if self.moves_since_rebuild >= self.bucket_size {
+            self.rebuild(self.buckets.concat());
+        }
+    }
+
+    pub fn mix(&mut self) {
+        for id in 0..self.len() {
+            self.move_number(id);
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = i64> + '_ {
+        self.buckets.iter().flatten().map(|&id| self.values[id])
+    }
+}
+
+// the numbers 1000, 2000 and 3000 after the zero
+fn grove_coordinates(list: &MixingList) -> i64 {
+    let mixed: Vec<_> = list.iter().collect();
+    // input_generator checked there's a zero
+    let zero = mixed.iter().position(|&v| v == 0).unwrap();
+
+    [1000, 2000, 3000]
+        .iter()
+        .map(|offset| mixed[(zero + offset) % mixed.len()])
+        .sum()
+}
+
+#[aoc_generator(day20)]
+pub fn input_generator(input: &str) -> Result<Vec<i64>, ParseError> {
+    let numbers = error::parse_lines(input, i64, "number")?;
+
+    // the grove coordinates are counted from the zero
+    if !numbers.contains(&0) {
+        return Err(ParseError::at(
+            input,
+            &input[input.len()..],
+            "a 0 in the list",
+        ));
+    }
+
+    Ok(numbers)
+}
+
+#[aoc(day20, part1)]
+pub fn solve_part1(input: &[i64]) -> i64 {
+    let mut list = MixingList::new(input.to_vec());
+    list.mix();
+    grove_coordinates(&list)
+}
+
+#[aoc(day20, part2)]
+pub fn solve_part2(input: &[i64]) -> i64 {
+    let mut list = MixingList::new(input.iter().map(|v| v * DECRYPTION_KEY).collect());
+
+    for _ in 0..10 {
+        list.mix();
+    }
+
+    grove_coordinates(&list)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "1
+2
+-3
+3
+-2
+0
+4";
+
+    // the list rotated to start at its first zero, since where a circular
+    // list "starts" is arbitrary
+    fn from_zero(values: impl Iterator<Item = i64>) -> Vec<i64> {
+        let mut values: Vec<_> = values.collect();
+        let zero = values.iter().position(|&v| v == 0).unwrap();
+        values.rotate_left(zero);
+        values
+    }
+
+    // the straightforward O(n) per move version
+    fn naive_mix(values: &[i64], rounds: usize) -> Vec<i64> {
+        let mut order: Vec<usize> = (0..values.len()).collect();
+
+        for _ in 0..rounds {
+            for id in 0..values.len() {
+                let pos = order.iter().position(|&x| x == id).unwrap();
+                order.remove(pos);
+                let target = (pos as i64 + values[id]).rem_euclid(values.len() as i64 - 1);
+                order.insert(target as usize, id);
+            }
+        }
+
+        order.into_iter().map(|id| values[id]).collect()
+    }
+
+    #[test]
+    fn mix() {
+        let mut list = MixingList::new(input_generator(EXAMPLE_INPUT).unwrap());
+        list.mix();
+        assert_eq!(from_zero(list.iter()), vec![0, 3, -2, 1, 2, -3, 4]);
+    }
+
+    #[test]
+    fn matches_naive_mix() {
+        // a cheap deterministic sequence with duplicates and big jumps
+        let values: Vec<i64> = (0..200i64).map(|i| (i * 7919 % 211) - 105).collect();
+
+        let mut list = MixingList::new(values.clone());
+        for _ in 0..3 {
+            list.mix();
+        }
+
+        assert_eq!(
+            from_zero(list.iter()),
+            from_zero(naive_mix(&values, 3).into_iter())
+        );
+    }
+
+    #[test]
+    fn examples_part1() {
+        assert_eq!(solve_part1(&input_generator(EXAMPLE_INPUT).unwrap()), 3);
+    }
+
+    #[test]
+    fn examples_part2() {
+        assert_eq!(
+            solve_part2(&input_generator(EXAMPLE_INPUT).unwrap()),
+            1623178306
+        );
+    }
+
+    #[test]
+    fn invalid_number() {
+        assert_eq!(
+            input_generator("1\n2x").err(),
+            Some(ParseError::new(2, 2, "x", "number"))
+        );
+    }
+
+    #[test]
+    fn no_zero() {
+        assert_eq!(
+            input_generator("1\n2\n-3").err(),
+            Some(ParseError::new(3, 3, "", "a 0 in the list"))
+        );
+        assert_eq!(
+            input_generator("").err(),
+            Some(ParseError::new(1, 1, "", "a 0 in the list"))
+        );
+    }
+}
@@ -0,0 +1,485 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alpha1, char, i64, one_of},
+    combinator::map,
+    sequence::{delimited, separated_pair, tuple},
+    IResult,
+};
+
+use crate::error::{self, ParseError};
+
+const ROOT: &str = "root";
+const HUMAN: &str = "humn";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Job {
+    Number(i64),
+    Op(String, Operator, String),
+}
+
+#[derive(Debug)]
+pub struct Monkeys(HashMap<String, Job>);
+
+// the unknown is what humn should yell; anything not involving it is
+// folded into a number as the tree is built
+#[derive(Debug, PartialEq, Eq)]
+pub enum Expr {
+    Number(i64),
+    Unknown,
+    Op(Box<Expr>, Operator, Box<Expr>),
+}
+
+// why a part couldn't find the number asked for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NoNumber {
+    Undefined,
+    IndependentOfHuman,
+    Unsolvable,
+}
+
+impl fmt::Display for NoNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Undefined => write!(f, "{ROOT}'s number divides by zero or overflows"),
+            Self::IndependentOfHuman => {
+                write!(f, "{ROOT}'s operands don't depend on {HUMAN}")
+            }
+            Self::Unsolvable => {
+                write!(
+                    f,
+                    "no integer value for {HUMAN} makes {ROOT}'s operands equal"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for NoNumber {}
+
+impl TryFrom<char> for Operator {
+    type Error = char;
+
+    fn try_from(ch: char) -> Result<Self, Self::Error> {
+        match ch {
+            '+' => Ok(Self::Add),
+            '-' => Ok(Self::Sub),
+            '*' => Ok(Self::Mul),
+            '/' => Ok(Self::Div),
+            other => Err(other),
+        }
+    }
+}
+
+impl Operator {
+    // `None` if it overflows or divides by zero
+    fn apply(self, l: i64, r: i64) -> Option<i64> {
+        match self {
+            Self::Add => l.checked_add(r),
+            Self::Sub => l.checked_sub(r),
+            Self::Mul => l.checked_mul(r),
+            Self::Div => l.checked_div(r),
+        }
+    }
+
+    // the x for which `x op r == result`
+    fn solve_left(self, r: i64, result: i64) -> Option<i64> {
+        let x = match self {
+            Self::Add => result.checked_sub(r),
+            Self::Sub => result.checked_add(r),
+            Self::Mul => result.checked_div(r),
+            Self::Div => result.checked_mul(r),
+        }?;
+
+        (self.apply(x, r) == Some(result)).then_some(x)
+    }
+
+    // the x for which `l op x == result`
+    fn solve_right(self, l: i64, result: i64) -> Option<i64> {
+        let x = match self {
+            Self::Add => result.checked_sub(l),
+            Self::Sub => l.checked_sub(result),
+            Self::Mul => result.checked_div(l),
+            // division truncates, so any divisor bigger than `l` gives 0,
+            // and otherwise the largest divisor that works is `l / result`
+            Self::Div if result == 0 => l.checked_abs()?.checked_add(1),
+            Self::Div => l.checked_div(result),
+        }?;
+
+        (self.apply(l, x) == Some(result)).then_some(x)
+    }
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self {
+            Self::Add => '+',
+            Self::Sub => '-',
+            Self::Mul => '*',
+            Self::Div => '/',
+        };
+        write!(f, "{symbol}")
+    }
+}
+
+impl Job {
+    fn parse(input: &str) -> IResult<&str, (String, Self)> {
+        map(
+            separated_pair(
+                alpha1,
+                tag(": "),
+                alt((
+                    map(i64, Self::Number),
+                    map(
+                        tuple((
+                            alpha1,
+                            delimited(char(' '), one_of("+-*/"), char(' ')),
+                            alpha1,
+                        )),
+                        |(l, op, r): (&str, char, &str)| {
+                            // one_of only lets through the four operators
+                            let op = Operator::try_from(op).unwrap();
+                            Self::Op(l.to_string(), op, r.to_string())
+                        },
+                    ),
+                )),
+            ),
+            |(name, job)| (name.to_string(), job),
+        )(input)
+    }
+}
+
+impl Monkeys {
+    pub fn expr(&self, name: &str, human_unknown: bool) -> Expr {
+        if human_unknown && name == HUMAN {
+            return Expr::Unknown;
+        }
+
+        match &self.0[name] {
+            Job::Number(n) => Expr::Number(*n),
+            Job::Op(l, op, r) => {
+                let (l, r) = (self.expr(l, human_unknown), self.expr(r, human_unknown));
+
+                // anything that can't be folded stays as it is, so evaluating
+                // it fails later
+                match (&l, &r) {
+                    (Expr::Number(l), Expr::Number(r)) => op.apply(*l, *r).map(Expr::Number),
+                    _ => None,
+                }
+                .unwrap_or_else(|| Expr::Op(Box::new(l), *op, Box::new(r)))
+            }
+        }
+    }
+
+    // the monkey closing a loop, and which of its operands leads back round
+    fn find_cycle<'a>(&'a self, names: &[&'a str]) -> Option<(&'a str, usize)> {
+        fn visit<'a>(
+            monkeys: &'a Monkeys,
+            name: &'a str,
+            on_stack: &mut HashSet<&'a str>,
+            done: &mut HashSet<&'a str>,
+        ) -> Option<(&'a str, usize)> {
+            if let Job::Op(l, _, r) = &monkeys.0[name] {
+                on_stack.insert(name);
+
+                for (i, operand) in [l, r].into_iter().enumerate() {
+                    if on_stack.contains(operand.as_str()) {
+                        return Some((name, i));
+                    }
+                    if !done.contains(operand.as_str()) {
+                        if let Some(cycle) = visit(monkeys, operand, on_stack, done) {
+                            return Some(cycle);
+                        }
+                    }
+                }
+
+                on_stack.remove(name);
+            }
+
+            done.insert(name);
+            None
+        }
+
+        let (mut on_stack, mut done) = (HashSet::new(), HashSet::new());
+        names
+            .iter()
+            .find_map(|name| visit(self, name, &mut on_stack, &mut done))
+    }
+}
+
+impl Expr {
+    pub fn evaluate(&self) -> Option<i64> {
+        match self {
+            Self::Number(n) => Some(*n),
+            Self::Unknown => None,
+            Self::Op(l, op, r) => op.apply(l.evaluate()?, r.evaluate()?),
+        }
+    }
+
+    // undoes each operation on the way down to the unknown
+    pub fn solve(&self, target: i64) -> Option<i64> {
+        match self {
+            Self::Number(_) => None,
+            Self::Unknown => Some(target),
+            Self::Op(l, op, r) => match (l.evaluate(), r.evaluate()) {
+                (None, Some(r)) => l.solve(op.solve_left(r, target)?),
+                (Some(l), None) => r.solve(op.solve_right(l, target)?),
+                _ => None,
+            },
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(n) => write!(f, "{n}"),
+            Self::Unknown => write!(f, "{HUMAN}"),
+            Self::Op(l, op, r) => write!(f, "({l} {op} {r})"),
+        }
+    }
+}
+
+#[aoc_generator(day21)]
+pub fn input_generator(input: &str) -> Result<Monkeys, ParseError> {
+    let jobs = error::parse_lines(
+        input,
+        Job::parse,
+        "'<name>: <number>' or '<name>: <name> <op> <name>'",
+    )?;
+    let names: Vec<_> = input
+        .lines()
+        .map(|line| line.split_once(": ").map_or(line, |(name, _)| name))
+        .collect();
+    let monkeys = Monkeys(jobs.into_iter().collect());
+
+    for (i, line) in input.lines().enumerate() {
+        let Some(Job::Op(l, _, r)) = line
+            .split_once(": ")
+            .and_then(|(name, _)| monkeys.0.get(name))
+        else {
+            continue;
+        };
+
+        for operand in [l, r] {
+            if !monkeys.0.contains_key(operand) {
+                let at = &line[line.rfind(operand.as_str()).unwrap()..];
+                return Err(ParseError::at(line, at, "name of a known monkey").shift_lines(i));
+            }
+        }
+    }
+
+    if let Some((name, operand)) = monkeys.find_cycle(&names) {
+        let i = names.iter().rposition(|&n| n == name).unwrap();
+        let line = input.lines().nth(i).unwrap();
+
+        // "<name>: <left> <op> <right>"
+        let at = match operand {
+            0 => &line[name.len() + 2..],
+            _ => &line[line.rfind(' ').unwrap() + 1..],
+        };
+        return Err(
+            ParseError::at(line, at, "monkey that doesn't depend on its own number").shift_lines(i),
+        );
+    }
+
+    for name in [ROOT, HUMAN] {
+        if !monkeys.0.contains_key(name) {
+            return Err(ParseError::at(
+                input,
+                &input[input.len()..],
+                format!("monkey '{name}'"),
+            ));
+        }
+    }
+
+    Ok(monkeys)
+}
+
+#[aoc(day21, part1)]
+pub fn solve_part1(input: &Monkeys) -> Result<i64, NoNumber> {
+    input
+        .expr(ROOT, false)
+        .evaluate()
+        .ok_or(NoNumber::Undefined)
+}
+
+#[aoc(day21, part2)]
+pub fn solve_part2(input: &Monkeys) -> Result<i64, NoNumber> {
+    // root checks its two operands for equality instead
+    let Expr::Op(l, _, r) = input.expr(ROOT, true) else {
+        return Err(NoNumber::IndependentOfHuman);
+    };
+
+    match (l.evaluate(), r.evaluate()) {
+        (None, Some(target)) => l.solve(target).ok_or(NoNumber::Unsolvable),
+        (Some(target), None) => r.solve(target).ok_or(NoNumber::Unsolvable),
+        (Some(_), Some(_)) => Err(NoNumber::IndependentOfHuman),
+        (None, None) => Err(NoNumber::Unsolvable),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "root: pppw + sjmn
+dbpl: 5
+cczh: sllz + lgvd
+zczc: 2
+ptdq: humn - dvpt
+dvpt: 3
+lfqf: 4
+humn: 5
+ljgn: 2
+sjmn: drzm * dbpl
+sllz: 4
+pppw: cczh / lfqf
+lgvd: ljgn * ptdq
+drzm: hmdt - zczc
+hmdt: 32";
+
+    #[test]
+    fn expr() {
+        let monkeys = input_generator(EXAMPLE_INPUT).unwrap();
+        assert_eq!(
+            monkeys.expr(ROOT, true).to_string(),
+            "(((4 + (2 * (humn - 3))) / 4) + 150)"
+        );
+        assert_eq!(monkeys.expr("sjmn", true), Expr::Number(150));
+    }
+
+    #[test]
+    fn solve() {
+        // (x - 3) * 2 = 10
+        let expr = Expr::Op(
+            Box::new(Expr::Op(
+                Box::new(Expr::Unknown),
+                Operator::Sub,
+                Box::new(Expr::Number(3)),
+            )),
+            Operator::Mul,
+            Box::new(Expr::Number(2)),
+        );
+        assert_eq!(expr.solve(10), Some(8));
+        assert_eq!(expr.solve(11), None);
+
+        // 12 / x = 4
+        let expr = Expr::Op(
+            Box::new(Expr::Number(12)),
+            Operator::Div,
+            Box::new(Expr::Unknown),
+        );
+        assert_eq!(expr.solve(4), Some(3));
+        assert_eq!(expr.solve(5), None);
+
+        // 7 / x = 2, truncating
+        let expr = Expr::Op(
+            Box::new(Expr::Number(7)),
+            Operator::Div,
+            Box::new(Expr::Unknown),
+        );
+        assert_eq!(expr.solve(2), Some(3));
+        assert_eq!(expr.solve(0), Some(8));
+        assert_eq!(expr.solve(-7), Some(-1));
+
+        // x / 0 = 0
+        let expr = Expr::Op(
+            Box::new(Expr::Unknown),
+            Operator::Div,
+            Box::new(Expr::Number(0)),
+        );
+        assert_eq!(expr.solve(0), None);
+
+        // x + 1 = i64::MIN
+        let expr = Expr::Op(
+            Box::new(Expr::Unknown),
+            Operator::Add,
+            Box::new(Expr::Number(1)),
+        );
+        assert_eq!(expr.solve(i64::MIN), None);
+        assert_eq!(Expr::Number(1).solve(1), None);
+    }
+
+    #[test]
+    fn examples_part1() {
+        assert_eq!(
+            solve_part1(&input_generator(EXAMPLE_INPUT).unwrap()),
+            Ok(152)
+        );
+    }
+
+    #[test]
+    fn examples_part2() {
+        assert_eq!(
+            solve_part2(&input_generator(EXAMPLE_INPUT).unwrap()),
+            Ok(301)
+        );
+    }
+
+    #[test]
+    fn division_by_zero() {
+        let monkeys =
+            input_generator("root: aaaa + humn\naaaa: humn / zero\nzero: 0\nhumn: 5").unwrap();
+        assert_eq!(monkeys.expr(ROOT, false).evaluate(), None);
+        assert_eq!(monkeys.expr(ROOT, false).to_string(), "((5 / 0) + 5)");
+        assert_eq!(monkeys.expr(ROOT, true).solve(10), None);
+        assert_eq!(solve_part1(&monkeys), Err(NoNumber::Undefined));
+
+        let monkeys =
+            input_generator("root: aaaa + bbbb\naaaa: humn / zero\nzero: 0\nbbbb: 3\nhumn: 5")
+                .unwrap();
+        assert_eq!(solve_part2(&monkeys), Err(NoNumber::Unsolvable));
+    }
+
+    #[test]
+    fn independent_of_human() {
+        let monkeys = input_generator("root: 5\nhumn: 5").unwrap();
+        assert_eq!(solve_part1(&monkeys), Ok(5));
+        assert_eq!(solve_part2(&monkeys), Err(NoNumber::IndependentOfHuman));
+
+        let monkeys = input_generator("root: aaaa + aaaa\naaaa: 2\nhumn: 5").unwrap();
+        assert_eq!(solve_part2(&monkeys), Err(NoNumber::IndependentOfHuman));
+    }
+
+    #[test]
+    fn cyclic_jobs() {
+        assert_eq!(
+            input_generator("root: aaaa + humn\naaaa: root * humn\nhumn: 5").err(),
+            Some(ParseError::new(
+                2,
+                7,
+                "root * humn",
+                "monkey that doesn't depend on its own number"
+            ))
+        );
+        assert_eq!(
+            input_generator("root: humn + bbbb\nhumn: 5\nbbbb: humn - bbbb").err(),
+            Some(ParseError::new(
+                3,
+                14,
+                "bbbb",
+                "monkey that doesn't depend on its own number"
+            ))
+        );
+    }
+
+    #[test]
+    fn unknown_monkey() {
+        assert_eq!(
+            input_generator("root: humn + abcd\nhumn: 5").err(),
+            Some(ParseError::new(1, 14, "abcd", "name of a known monkey"))
+        );
+    }
+}
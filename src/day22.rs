@@ -0,0 +1,453 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+use nom::{
+    branch::alt,
+    character::complete::{char, u32},
+    combinator::map,
+    multi::many1,
+    IResult,
+};
+
+use crate::error::{self, ParseError};
+use crate::grid::{Direction, Grid, Pos};
+use crate::point::Point3;
+
+type Vector = Point3<i32>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Void,
+    Open,
+    Wall,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Forward(u32),
+    Left,
+    Right,
+}
+
+// in the order the password scores them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Facing {
+    Right,
+    Down,
+    Left,
+    Up,
+}
+
+const FACINGS: [Facing; 4] = [Facing::Right, Facing::Down, Facing::Left, Facing::Up];
+
+type State = (Pos, Facing);
+
+// part 2 couldn't fold the board's net into a cube
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotACube;
+
+impl fmt::Display for NotACube {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the board doesn't fold into a cube")
+    }
+}
+
+impl std::error::Error for NotACube {}
+
+#[derive(Debug)]
+pub struct Notes {
+    board: Grid<Tile>,
+    path: Vec<Step>,
+}
+
+// how a face of the net sits on the cube, with `right` and `down` its net
+// directions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Frame {
+    right: Vector,
+    down: Vector,
+    normal: Vector,
+}
+
+#[derive(Debug)]
+struct Cube {
+    side: usize,
+    // keyed by the face's position on the net, in units of `side`
+    faces: HashMap<(usize, usize), Frame>,
+}
+
+impl Step {
+    fn parse(input: &str) -> IResult<&str, Self> {
+        alt((
+            map(u32, Self::Forward),
+            map(char('L'), |_| Self::Left),
+            map(char('R'), |_| Self::Right),
+        ))(input)
+    }
+}
+
+impl Facing {
+    fn turn_left(self) -> Self {
+        FACINGS[(self as usize + 3) % 4]
+    }
+
+    fn turn_right(self) -> Self {
+        FACINGS[(self as usize + 1) % 4]
+    }
+
+    fn direction(self) -> Direction {
+        match self {
+            Self::Right => Direction::Right,
+            Self::Down => Direction::Down,
+            Self::Left => Direction::Left,
+            Self::Up => Direction::Up,
+        }
+    }
+}
+
+impl Frame {
+    // walking off an edge carries on into the cube, so the old normal
+    // becomes the new walking direction
+    fn neighbor(&self, facing: Facing) -> Self {
+        let Self {
+            right,
+            down,
+            normal,
+        } = *self;
+
+        match facing {
+            Facing::Right => Self {
+                right: -normal,
+                down,
+                normal: right,
+            },
+            Facing::Left => Self {
+                right: normal,
+                down,
+                normal: -right,
+            },
+            Facing::Down => Self {
+                right,
+                down: -normal,
+                normal: down,
+            },
+            Facing::Up => Self {
+                right,
+                down: normal,
+                normal: -down,
+            },
+        }
+    }
+
+    fn vector(&self, facing: Facing) -> Vector {
+        match facing {
+            Facing::Right => self.right,
+            Facing::Down => self.down,
+            Facing::Left => -self.right,
+            Facing::Up => -self.down,
+        }
+    }
+}
+
+impl Cube {
+    fn fold(board: &Grid<Tile>) -> Option<Self> {
+        let tiles = board.cells().iter().filter(|&&t| t != Tile::Void).count();
+        let side = (1..).find(|s| 6 * s * s >= tiles)?;
+        if 6 * side * side != tiles {
+            return None;
+        }
+
+        let on_net = |(fx, fy): (usize, usize)| {
+            board
+                .get((fx * side, fy * side))
+                .is_some_and(|&t| t != Tile::Void)
+        };
+
+        let first = (0..board.width() / side)
+            .map(|fx| (fx, 0))
+            .find(|&f| on_net(f))?;
+
+        let mut faces = HashMap::from([(
+            first,
+            Frame {
+                right: Vector::new(1, 0, 0),
+                down: Vector::new(0, 1, 0),
+                normal: Vector::new(0, 0, 1),
+            },
+        )]);
+        let mut queue = VecDeque::from([first]);
+
+        while let Some(face) = queue.pop_front() {
+            let frame = faces[&face];
+
+            for facing in FACINGS {
+                let (dx, dy) = facing.direction().delta();
+                let Some(next) = face
+                    .0
+                    .checked_add_signed(dx)
+                    .zip(face.1.checked_add_signed(dy))
+                else {
+                    continue;
+                };
+
+                if on_net(next) && !faces.contains_key(&next) {
+                    faces.insert(next, frame.neighbor(facing));
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        // six faces that aren't all connected, or that overlap when folded
+        let mut normals: Vec<_> = faces.values().map(|f| f.normal).collect();
+        normals.sort();
+        normals.dedup();
+        (faces.len() == 6 && normals.len() == 6).then_some(Self { side, faces })
+    }
+
+    fn step(&self, ((x, y), facing): State) -> State {
+        let s = self.side;
+        let face = (x / s, y / s);
+        let (i, j) = (x % s, y % s);
+
+        let (dx, dy) = facing.direction().delta();
+        if let Some((i, j)) = i.checked_add_signed(dx).zip(j.checked_add_signed(dy)) {
+            if i < s && j < s {
+                return ((face.0 * s + i, face.1 * s + j), facing);
+            }
+        }
+
+        // Place the cube with its centre at the origin, doubling lengths so
+        // tile centres land on integers. Walking off the edge rounds it and
+        // heads into the face whose normal points the way we were going.
+        let from = self.faces[&face];
+        let heading = from.vector(facing);
+        let s = s as i32;
+        let here = from.normal * s
+            + from.right * (2 * i as i32 + 1 - s)
+            + from.down * (2 * j as i32 + 1 - s);
+        let there = here + heading - from.normal;
+
+        let (&to_face, to) = self
+            .faces
+            .iter()
+            .find(|(_, f)| f.normal == heading)
+            .unwrap();
+        let i = ((there.dot(&to.right) + s - 1) / 2) as usize;
+        let j = ((there.dot(&to.down) + s - 1) / 2) as usize;
+        let facing = FACINGS
+            .into_iter()
+            .find(|&f| to.vector(f) == -from.normal)
+            .unwrap();
+
+        let s = self.side;
+        ((to_face.0 * s + i, to_face.1 * s + j), facing)
+    }
+}
+
+impl Notes {
+    fn start(&self) -> State {
+        let x = self.board.row(0).position(|&t| t == Tile::Open).unwrap();
+        ((x, 0), Facing::Right)
+    }
+
+    fn step_flat(&self, (pos, facing): State) -> State {
+        if let Some(next) = self.board.step(pos, facing.direction()) {
+            if self.board[next] != Tile::Void {
+                return (next, facing);
+            }
+        }
+
+        let back = facing.turn_left().turn_left().direction();
+        let (wrapped, _) = self
+            .board
+            .ray(pos, back)
+            .take_while(|(_, &t)| t != Tile::Void)
+            .last()
+            .unwrap_or((pos, &Tile::Open));
+
+        (wrapped, facing)
+    }
+
+    fn walk(&self, step: impl Fn(State) -> State) -> usize {
+        let mut state = self.start();
+
+        for instruction in &self.path {
+            match instruction {
+                Step::Left => state.1 = state.1.turn_left(),
+                Step::Right => state.1 = state.1.turn_right(),
+                Step::Forward(n) => {
+                    for _ in 0..*n {
+                        let next = step(state);
+                        if self.board[next.0] == Tile::Wall {
+                            break;
+                        }
+                        state = next;
+                    }
+                }
+            }
+        }
+
+        let ((x, y), facing) = state;
+        1000 * (y + 1) + 4 * (x + 1) + facing as usize
+    }
+}
+
+#[aoc_generator(day22)]
+pub fn input_generator(input: &str) -> Result<Notes, ParseError> {
+    let (board, path) = input.split_once("\n\n").ok_or_else(|| {
+        ParseError::at(
+            input,
+            &input[input.len()..],
+            "blank line between the board and the path",
+        )
+    })?;
+
+    // rows stop after their last tile, so pad them out to a rectangle
+    let width = board.lines().map(str::len).max().unwrap_or(0);
+    let padded: String = board
+        .lines()
+        .map(|l| format!("{l:width$}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let board = Grid::parse(
+        &padded,
+        |c| match c {
+            ' ' => Some(Tile::Void),
+            '.' => Some(Tile::Open),
+            '#' => Some(Tile::Wall),
+            _ => None,
+        },
+        "' ', '.' or '#'",
+    )?;
+
+    if !board.row(0).any(|&t| t == Tile::Open) {
+        return Err(ParseError::new(1, 1, "", "open tile on the top row"));
+    }
+
+    let path = error::finish(
+        input,
+        many1(Step::parse)(path),
+        "number of tiles, 'L' or 'R'",
+    )?;
+
+    Ok(Notes { board, path })
+}
+
+#[aoc(day22, part1)]
+pub fn solve_part1(input: &Notes) -> usize {
+    input.walk(|state| input.step_flat(state))
+}
+
+#[aoc(day22, part2)]
+pub fn solve_part2(input: &Notes) -> Result<usize, NotACube> {
+    let cube = Cube::fold(&input.board).ok_or(NotACube)?;
+    Ok(input.walk(|state| cube.step(state)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "        ...#
+        .#..
+        #...
+        ....
+...#.......#
+........#...
+..#....#....
+..........#.
+        ...#....
+        .....#..
+        .#......
+        ......#.
+
+10R5L5R10L4R5L5";
+
+    // a net of open faces with `side` tiles, laid out by a picture of
+    // which faces are present
+    fn open_net(layout: &str, side: usize) -> Grid<Tile> {
+        let rows: Vec<String> = layout
+            .lines()
+            .flat_map(|row| {
+                let row: String = row
+                    .chars()
+                    .flat_map(|c| {
+                        let tile = if c == '#' { '.' } else { ' ' };
+                        std::iter::repeat_n(tile, side)
+                    })
+                    .collect();
+                std::iter::repeat_n(row, side)
+            })
+            .collect();
+
+        input_generator(&format!("{}\n\n1", rows.join("\n")))
+            .unwrap()
+            .board
+    }
+
+    #[test]
+    fn fold() {
+        let notes = input_generator(EXAMPLE_INPUT).unwrap();
+        let cube = Cube::fold(&notes.board).unwrap();
+        assert_eq!(cube.side, 4);
+        assert_eq!(cube.faces.len(), 6);
+
+        // from the example's description of part 2
+        assert_eq!(cube.step(((11, 5), Facing::Right)), ((14, 8), Facing::Down));
+        assert_eq!(cube.step(((10, 11), Facing::Down)), ((1, 7), Facing::Up));
+
+        assert!(Cube::fold(&open_net("####\n####", 2)).is_none());
+        assert!(Cube::fold(&open_net("###\n###", 2)).is_none());
+    }
+
+    #[test]
+    fn walking_straight_goes_around_the_cube() {
+        // the example's layout, the usual real input layout, and a cross
+        for layout in ["  #\n###\n  ##", " ##\n #\n##\n#", " #\n###\n #\n #"] {
+            let side = 3;
+            let board = open_net(layout, side);
+            let cube = Cube::fold(&board).unwrap();
+
+            for (pos, _) in board.iter().filter(|(_, &t)| t == Tile::Open) {
+                for facing in FACINGS {
+                    let start = (pos, facing);
+                    let mut state = start;
+                    for _ in 0..4 * side {
+                        state = cube.step(state);
+                        assert_ne!(board[state.0], Tile::Void, "{layout:?} from {start:?}");
+                    }
+                    assert_eq!(state, start, "{layout:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn examples_part1() {
+        assert_eq!(solve_part1(&input_generator(EXAMPLE_INPUT).unwrap()), 6032);
+    }
+
+    #[test]
+    fn examples_part2() {
+        assert_eq!(
+            solve_part2(&input_generator(EXAMPLE_INPUT).unwrap()),
+            Ok(5031)
+        );
+    }
+
+    #[test]
+    fn not_a_cube() {
+        let notes = input_generator("..\n..\n\n1R1").unwrap();
+        assert_eq!(solve_part1(&notes), 2009);
+        assert_eq!(solve_part2(&notes), Err(NotACube));
+    }
+
+    #[test]
+    fn invalid_path() {
+        assert_eq!(
+            input_generator("..\n..\n\n10R5X").err(),
+            Some(ParseError::new(4, 5, "X", "number of tiles, 'L' or 'R'"))
+        );
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+
+use crate::error::ParseError;
+use crate::grid::{Direction, Grid};
+use crate::point::Point;
+
+type Elf = Point<i32>;
+
+// each direction an elf can propose, with the three cells that must be
+// empty for it to do so
+const PROPOSALS: [(Direction, [Direction; 3]); 4] = [
+    (
+        Direction::Up,
+        [Direction::UpLeft, Direction::Up, Direction::UpRight],
+    ),
+    (
+        Direction::Down,
+        [Direction::DownLeft, Direction::Down, Direction::DownRight],
+    ),
+    (
+        Direction::Left,
+        [Direction::UpLeft, Direction::Left, Direction::DownLeft],
+    ),
+    (
+        Direction::Right,
+        [Direction::UpRight, Direction::Right, Direction::DownRight],
+    ),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grove {
+    elves: HashSet<Elf>,
+    // rounds played so far, which decides the first direction considered
+    rounds: usize,
+}
+
+fn step(elf: Elf, dir: Direction) -> Elf {
+    let (dx, dy) = dir.delta();
+    Elf::new(elf.x + dx as i32, elf.y + dy as i32)
+}
+
+impl Grove {
+    fn proposal(&self, elf: Elf) -> Option<Elf> {
+        let occupied = |dir| self.elves.contains(&step(elf, dir));
+
+        if !Direction::ALL.into_iter().any(occupied) {
+            return None;
+        }
+
+        (0..PROPOSALS.len())
+            .map(|i| PROPOSALS[(self.rounds + i) % PROPOSALS.len()])
+            .find(|(_, checks)| !checks.iter().any(|&d| occupied(d)))
+            .map(|(dir, _)| step(elf, dir))
+    }
+
+    // whether any elf moved
+    pub fn round(&mut self) -> bool {
+        // proposed destination -> the elves proposing it
+        let mut proposals: HashMap<Elf, Vec<Elf>> = HashMap::new();
+
+        for &elf in &self.elves {
+            if let Some(to) = self.proposal(elf) {
+                proposals.entry(to).or_default().push(elf);
+            }
+        }
+
+        let mut moved = false;
+
+        for (to, from) in proposals {
+            if let [from] = from[..] {
+                self.elves.remove(&from);
+                self.elves.insert(to);
+                moved = true;
+            }
+        }
+
+        self.rounds += 1;
+        moved
+    }
+
+    // top left and bottom right corners
+    fn bounds(&self) -> (Elf, Elf) {
+        let (min_x, max_x) = self
+            .elves
+            .iter()
+            .map(|e| e.x)
+            .minmax()
+            .into_option()
+            .unwrap_or_default();
+        let (min_y, max_y) = self
+            .elves
+            .iter()
+            .map(|e| e.y)
+            .minmax()
+            .into_option()
+            .unwrap_or_default();
+
+        (Elf::new(min_x, min_y), Elf::new(max_x, max_y))
+    }
+
+    fn empty_ground(&self) -> usize {
+        let (min, max) = self.bounds();
+        let area = ((max.x - min.x + 1) * (max.y - min.y + 1)) as usize;
+        area - self.elves.len()
+    }
+
+    pub fn render(&self) -> String {
+        let (min, max) = self.bounds();
+
+        (min.y..=max.y)
+            .map(|y| {
+                (min.x..=max.x)
+                    .map(|x| {
+                        if self.elves.contains(&Elf::new(x, y)) {
+                            '#'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .join("\n")
+    }
+}
+
+#[aoc_generator(day23)]
+pub fn input_generator(input: &str) -> Result<Grove, ParseError> {
+    let grid = Grid::parse(
+        input,
+        |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        },
+        "'#' or '.'",
+    )?;
+
+    let elves = grid
+        .iter()
+        .filter(|(_, &elf)| elf)
+        .map(|((x, y), _)| Elf::new(x as i32, y as i32))
+        .collect();
+
+    Ok(Grove { elves, rounds: 0 })
+}
+
+#[aoc(day23, part1)]
+pub fn solve_part1(input: &Grove) -> usize {
+    let mut grove = input.clone();
+
+    for _ in 0..10 {
+        grove.round();
+    }
+
+    grove.empty_ground()
+}
+
+#[aoc(day23, part2)]
+pub fn solve_part2(input: &Grove) -> usize {
+    let mut grove = input.clone();
+    while grove.round() {}
+    grove.rounds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "....#..
+..###.#
+#...#.#
+.#...##
+#.###..
+##.#.##
+.#..#..";
+
+    #[test]
+    fn small_example() {
+        let mut grove = input_generator(".....\n..##.\n..#..\n.....\n..##.\n.....").unwrap();
+
+        grove.round();
+        assert_eq!(grove.render(), "##\n..\n#.\n.#\n#.");
+
+        grove.round();
+        assert_eq!(grove.render(), ".##.\n#...\n...#\n....\n.#..");
+
+        grove.round();
+        assert_eq!(grove.render(), "..#..\n....#\n#....\n....#\n.....\n..#..");
+
+        assert!(!grove.round());
+        assert_eq!(grove.rounds, 4);
+    }
+
+    #[test]
+    fn examples_part1() {
+        assert_eq!(solve_part1(&input_generator(EXAMPLE_INPUT).unwrap()), 110);
+    }
+
+    #[test]
+    fn examples_part2() {
+        assert_eq!(solve_part2(&input_generator(EXAMPLE_INPUT).unwrap()), 20);
+    }
+
+    #[test]
+    fn invalid_ground() {
+        assert_eq!(
+            input_generator("..#\n.x.").err(),
+            Some(ParseError::new(2, 2, "x.", "'#' or '.'"))
+        );
+    }
+}
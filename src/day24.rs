@@ -0,0 +1,238 @@
+use std::fmt;
+
+use crate::error::ParseError;
+use crate::grid::{Direction, Grid, Pos};
+use crate::search;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Wall,
+    Ground,
+    Blizzard(Direction),
+}
+
+#[derive(Debug)]
+pub struct Valley {
+    walls: Grid<bool>,
+    // occupied[t % occupied.len()] = where the blizzards are at minute t;
+    // they line up again every lcm(width, height) minutes
+    occupied: Vec<Grid<bool>>,
+    start: Pos,
+    goal: Pos,
+}
+
+// the blizzards never leave a way through
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoRoute;
+
+impl fmt::Display for NoRoute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no route through the blizzards")
+    }
+}
+
+impl std::error::Error for NoRoute {}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+fn lcm(a: usize, b: usize) -> usize {
+    a / gcd(a, b) * b
+}
+
+impl Valley {
+    fn new(cells: &Grid<Cell>, start: Pos, goal: Pos) -> Self {
+        // the blizzards move around inside the walls
+        let (width, height) = (cells.width() - 2, cells.height() - 2);
+        let period = lcm(width, height);
+
+        let occupied = (0..period)
+            .map(|t| {
+                let mut occupied = Grid::new(cells.width(), cells.height(), false);
+
+                for ((x, y), cell) in cells.iter() {
+                    let Cell::Blizzard(dir) = cell else {
+                        continue;
+                    };
+
+                    let (dx, dy) = dir.delta();
+                    let x = (x as isize - 1 + dx * t as isize).rem_euclid(width as isize);
+                    let y = (y as isize - 1 + dy * t as isize).rem_euclid(height as isize);
+                    occupied[(x as usize + 1, y as usize + 1)] = true;
+                }
+
+                occupied
+            })
+            .collect();
+
+        Self {
+            walls: Grid::from_vec(
+                cells.width(),
+                cells.cells().iter().map(|&c| c == Cell::Wall).collect(),
+            ),
+            occupied,
+            start,
+            goal,
+        }
+    }
+
+    // searches positions paired with where the blizzards are in their
+    // cycle, since that's all that decides where we can go next
+    fn crossing(&self, from: Pos, to: Pos, departure: usize) -> Option<usize> {
+        let period = self.occupied.len();
+
+        let valley = search::from_fn(|&(pos, t): &(Pos, usize)| {
+            let t = (t + 1) % period;
+            let occupied = &self.occupied[t];
+
+            std::iter::once(pos)
+                .chain(self.walls.neighbors4(pos))
+                .filter(|&p| !self.walls[p] && !occupied[p])
+                .map(move |p| ((p, t), 1))
+                .collect::<Vec<_>>()
+        });
+
+        search::bfs(&valley, (from, departure % period), |&(pos, _)| pos == to).goal_distance()
+    }
+}
+
+#[aoc_generator(day24)]
+pub fn input_generator(input: &str) -> Result<Valley, ParseError> {
+    let cells = Grid::parse(
+        input,
+        |c| match c {
+            '#' => Some(Cell::Wall),
+            '.' => Some(Cell::Ground),
+            '>' => Some(Cell::Blizzard(Direction::Right)),
+            '<' => Some(Cell::Blizzard(Direction::Left)),
+            '^' => Some(Cell::Blizzard(Direction::Up)),
+            'v' => Some(Cell::Blizzard(Direction::Down)),
+            _ => None,
+        },
+        "'#', '.', '>', '<', '^' or 'v'",
+    )?;
+
+    // the only gaps in the top and bottom walls
+    let gap = |y: usize| {
+        let mut row = cells.row(y);
+        match (
+            row.position(|&c| c == Cell::Ground),
+            row.position(|&c| c == Cell::Ground),
+        ) {
+            (Some(x), None) => Ok((x, y)),
+            _ => Err(ParseError::new(
+                y + 1,
+                1,
+                input.lines().nth(y).unwrap_or(""),
+                "wall with a single gap",
+            )),
+        }
+    };
+
+    if cells.width() < 3 || cells.height() < 3 {
+        return Err(ParseError::new(1, 1, input, "valley inside walls"));
+    }
+
+    let start = gap(0)?;
+    let goal = gap(cells.height() - 1)?;
+
+    Ok(Valley::new(&cells, start, goal))
+}
+
+#[aoc(day24, part1)]
+pub fn solve_part1(input: &Valley) -> Result<usize, NoRoute> {
+    input.crossing(input.start, input.goal, 0).ok_or(NoRoute)
+}
+
+#[aoc(day24, part2)]
+pub fn solve_part2(input: &Valley) -> Result<usize, NoRoute> {
+    let there = input.crossing(input.start, input.goal, 0).ok_or(NoRoute)?;
+    let back = input
+        .crossing(input.goal, input.start, there)
+        .ok_or(NoRoute)?;
+    let again = input
+        .crossing(input.start, input.goal, there + back)
+        .ok_or(NoRoute)?;
+
+    Ok(there + back + again)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "#.######
+#>>.<^<#
+#.<..<<#
+#>v.><>#
+#<^v^^>#
+######.#";
+
+    #[test]
+    fn blizzards() {
+        let valley =
+            input_generator("#.#####\n#.....#\n#>....#\n#.....#\n#...v.#\n#.....#\n#####.#")
+                .unwrap();
+        assert_eq!(valley.occupied.len(), 5);
+
+        let occupied = |t: usize| {
+            valley.occupied[t % 5]
+                .iter()
+                .filter(|(_, &o)| o)
+                .map(|(p, _)| p)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(occupied(0), vec![(1, 2), (4, 4)]);
+        assert_eq!(occupied(1), vec![(2, 2), (4, 5)]);
+        // both blizzards in the same place
+        assert_eq!(occupied(3), vec![(4, 2)]);
+        assert_eq!(occupied(5), occupied(0));
+    }
+
+    #[test]
+    fn examples_part1() {
+        assert_eq!(
+            solve_part1(&input_generator(EXAMPLE_INPUT).unwrap()),
+            Ok(18)
+        );
+    }
+
+    #[test]
+    fn examples_part2() {
+        assert_eq!(
+            solve_part2(&input_generator(EXAMPLE_INPUT).unwrap()),
+            Ok(54)
+        );
+    }
+
+    #[test]
+    fn no_route() {
+        // the two blizzards swap places every minute, filling the valley
+        let valley = input_generator("#.#\n#v#\n#^#\n#.#").unwrap();
+        assert_eq!(solve_part1(&valley), Err(NoRoute));
+        assert_eq!(solve_part2(&valley), Err(NoRoute));
+    }
+
+    #[test]
+    fn invalid_valley() {
+        assert_eq!(
+            input_generator("#.###\n#.x.#\n###.#").err(),
+            Some(ParseError::new(
+                2,
+                3,
+                "x.#",
+                "'#', '.', '>', '<', '^' or 'v'"
+            ))
+        );
+        assert_eq!(
+            input_generator("#..##\n#...#\n###.#").err(),
+            Some(ParseError::new(1, 1, "#..##", "wall with a single gap"))
+        );
+    }
+}
@@ -0,0 +1,304 @@
+use std::fmt;
+use std::iter::Sum;
+use std::ops::Add;
+use std::str::FromStr;
+
+use crate::error::ParseError;
+
+// balanced base five, with digits '=' (-2), '-' (-1), '0', '1' and '2'
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Snafu(i128);
+
+// the fuel requirements add up to more than a SNAFU number holds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SumOverflow;
+
+impl fmt::Display for SumOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the sum doesn't fit in a SNAFU number")
+    }
+}
+
+impl std::error::Error for SumOverflow {}
+
+// n * 5 + digit, stepping n towards zero first if n * 5 alone overflows but
+// the result would still fit
+fn push_digit(n: i128, digit: i128) -> Option<i128> {
+    match n.checked_mul(5) {
+        Some(shifted) => shifted.checked_add(digit),
+        None => {
+            let toward_zero = n.signum();
+            (n - toward_zero)
+                .checked_mul(5)?
+                .checked_add(digit + 5 * toward_zero)
+        }
+    }
+}
+
+impl FromStr for Snafu {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseError::new(
+                1,
+                1,
+                "",
+                "SNAFU digit '=', '-', '0', '1' or '2'",
+            ));
+        }
+
+        s.char_indices()
+            .try_fold(0i128, |n, (i, c)| {
+                let digit = match c {
+                    '=' => -2,
+                    '-' => -1,
+                    '0' => 0,
+                    '1' => 1,
+                    '2' => 2,
+                    _ => {
+                        return Err(ParseError::at(
+                            s,
+                            &s[i..],
+                            "SNAFU digit '=', '-', '0', '1' or '2'",
+                        ))
+                    }
+                };
+
+                push_digit(n, digit)
+                    .ok_or_else(|| ParseError::at(s, s, "SNAFU number that fits in an i128"))
+            })
+            .map(Self)
+    }
+}
+
+impl fmt::Display for Snafu {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 == 0 {
+            return write!(f, "0");
+        }
+
+        let mut n = self.0;
+        let mut digits = Vec::new();
+
+        while n != 0 {
+            // pick the digit in -2..=2 that leaves a multiple of five,
+            // carrying one into the next digit when it's negative
+            let (digit, carry) = match n.rem_euclid(5) {
+                r @ 0..=2 => (r, 0),
+                r => (r - 5, 1),
+            };
+
+            digits.push(match digit {
+                -2 => '=',
+                -1 => '-',
+                0 => '0',
+                1 => '1',
+                _ => '2',
+            });
+
+            n = n.div_euclid(5) + carry;
+        }
+
+        write!(f, "{}", digits.iter().rev().collect::<String>())
+    }
+}
+
+impl Snafu {
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self)
+    }
+}
+
+impl Add for Snafu {
+    type Output = Self;
+
+    // panics if the sum doesn't fit, see `checked_add`
+    fn add(self, rhs: Self) -> Self {
+        self.checked_add(rhs).expect("SNAFU sum overflowed")
+    }
+}
+
+impl Sum for Snafu {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
+
+impl From<i64> for Snafu {
+    fn from(n: i64) -> Self {
+        Self(n.into())
+    }
+}
+
+impl From<i128> for Snafu {
+    fn from(n: i128) -> Self {
+        Self(n)
+    }
+}
+
+impl TryFrom<Snafu> for i64 {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(n: Snafu) -> Result<Self, Self::Error> {
+        n.0.try_into()
+    }
+}
+
+impl From<Snafu> for i128 {
+    fn from(n: Snafu) -> Self {
+        n.0
+    }
+}
+
+#[aoc_generator(day25)]
+pub fn input_generator(input: &str) -> Result<Vec<Snafu>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, l)| l.parse().map_err(|e: ParseError| e.shift_lines(i)))
+        .collect()
+}
+
+#[aoc(day25, part1)]
+pub fn solve_part1(input: &[Snafu]) -> Result<String, SumOverflow> {
+    input
+        .iter()
+        .try_fold(Snafu::default(), |sum, &n| sum.checked_add(n))
+        .map(|sum| sum.to_string())
+        .ok_or(SumOverflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "1=-0-2
+12111
+2=0=
+21
+2=01
+111
+20012
+112
+1=-1=
+1-12
+12
+1=
+122";
+
+    #[test]
+    fn conversions() {
+        for (decimal, snafu) in [
+            (1i64, "1"),
+            (2, "2"),
+            (3, "1="),
+            (4, "1-"),
+            (5, "10"),
+            (8, "2="),
+            (10, "20"),
+            (15, "1=0"),
+            (20, "1-0"),
+            (2022, "1=11-2"),
+            (12345, "1-0---0"),
+            (314159265, "1121-1110-1=0"),
+        ] {
+            assert_eq!(Snafu::from(decimal).to_string(), snafu);
+            assert_eq!(i64::try_from(snafu.parse::<Snafu>().unwrap()), Ok(decimal));
+        }
+    }
+
+    #[test]
+    fn extremes() {
+        for n in [i128::MIN, i128::MIN + 1, -1, 0, 1, i128::MAX] {
+            let snafu = Snafu::from(n);
+            assert_eq!(snafu.to_string().parse(), Ok(snafu), "{n}");
+            assert_eq!(i128::from(snafu), n);
+        }
+
+        assert!(i64::try_from(Snafu::from(i64::MAX) + Snafu::from(1i64)).is_err());
+        assert_eq!(Snafu::from(i128::MAX).checked_add(Snafu::from(1i128)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "SNAFU sum overflowed")]
+    fn add_overflow() {
+        let _ = Snafu::from(i128::MIN) + Snafu::from(-1i128);
+    }
+
+    proptest! {
+        #[test]
+        fn parse_display_i64(n: i64) {
+            let snafu = Snafu::from(n);
+            prop_assert_eq!(snafu.to_string().parse(), Ok(snafu));
+            prop_assert_eq!(i64::try_from(snafu), Ok(n));
+        }
+
+        #[test]
+        fn parse_display_i128(n: i128) {
+            let snafu = Snafu::from(n);
+            prop_assert_eq!(snafu.to_string().parse(), Ok(snafu));
+            prop_assert_eq!(i128::from(snafu), n);
+        }
+
+        // short enough to always fit in an i128
+        #[test]
+        fn display_parse(s in "[=\\-012]{1,50}") {
+            let canonical = match s.trim_start_matches('0') {
+                "" => "0",
+                trimmed => trimmed,
+            };
+            prop_assert_eq!(s.parse::<Snafu>().unwrap().to_string(), canonical);
+        }
+
+        #[test]
+        fn add_i64(a: i64, b: i64) {
+            prop_assert_eq!(
+                Snafu::from(a) + Snafu::from(b),
+                Snafu::from(i128::from(a) + i128::from(b))
+            );
+        }
+
+        #[test]
+        fn checked_add_i128(a: i128, b: i128) {
+            prop_assert_eq!(
+                Snafu::from(a).checked_add(Snafu::from(b)),
+                a.checked_add(b).map(Snafu::from)
+            );
+        }
+    }
+
+    #[test]
+    fn examples_part1() {
+        assert_eq!(
+            solve_part1(&input_generator(EXAMPLE_INPUT).unwrap()),
+            Ok("2=-1=0".to_string())
+        );
+    }
+
+    #[test]
+    fn sum_overflow() {
+        let max = Snafu::from(i128::MAX).to_string();
+        let input = input_generator(&format!("{max}\n-\n1")).unwrap();
+        assert_eq!(solve_part1(&input), Ok(max.clone()));
+
+        let input = input_generator(&format!("{max}\n1\n-")).unwrap();
+        assert_eq!(solve_part1(&input), Err(SumOverflow));
+    }
+
+    #[test]
+    fn invalid_snafu() {
+        assert_eq!(
+            input_generator("1=\n12x1").err(),
+            Some(ParseError::new(
+                2,
+                3,
+                "x1",
+                "SNAFU digit '=', '-', '0', '1' or '2'"
+            ))
+        );
+        assert!("2".repeat(60).parse::<Snafu>().is_err());
+    }
+}
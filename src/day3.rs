@@ -1,6 +1,8 @@
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
 
+use crate::error::ParseError;
+
 struct Compartment(HashMap<char, u32>);
 
 struct Rucksack {
@@ -39,14 +41,24 @@ impl Rucksack {
     }
 }
 
-impl From<&str> for Rucksack {
-    fn from(s: &str) -> Self {
+impl TryFrom<&str> for Rucksack {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        if let Some(pos) = s.find(|ch: char| !ch.is_ascii_alphabetic()) {
+            return Err(ParseError::at(s, &s[pos..], "item letter"));
+        }
+
+        if !s.len().is_multiple_of(2) {
+            return Err(ParseError::at(s, &s[s.len()..], "even number of items"));
+        }
+
         let (first, second) = s.split_at(s.len() / 2);
 
-        Self {
+        Ok(Self {
             first: Compartment::from(first),
             second: Compartment::from(second),
-        }
+        })
     }
 }
 
@@ -93,33 +105,37 @@ impl Group {
     }
 }
 
-fn parse1(input: &str) -> Vec<Rucksack> {
-    input.lines().map(Rucksack::from).collect()
+fn parse_rucksack((i, line): (usize, &str)) -> Result<Rucksack, ParseError> {
+    Rucksack::try_from(line).map_err(|e| e.shift_lines(i))
 }
 
-fn parse2(input: &str) -> Vec<Group> {
+fn parse1(input: &str) -> Result<Vec<Rucksack>, ParseError> {
+    input.lines().enumerate().map(parse_rucksack).collect()
+}
+
+fn parse2(input: &str) -> Result<Vec<Group>, ParseError> {
     let mut groups = Vec::new();
 
-    for group in &input.lines().chunks(3) {
-        let rucksacks = group.map(Rucksack::from).collect::<Vec<_>>();
+    for group in &input.lines().enumerate().chunks(3) {
+        let rucksacks = group.map(parse_rucksack).collect::<Result<Vec<_>, _>>()?;
         groups.push(Group::from(rucksacks));
     }
 
-    groups
+    Ok(groups)
 }
 
 #[aoc(day3, part1)]
-pub fn solve_part1(input: &str) -> u32 {
-    let rucksacks = parse1(input);
+pub fn solve_part1(input: &str) -> Result<u32, ParseError> {
+    let rucksacks = parse1(input)?;
 
-    rucksacks.iter().map(|rs| rs.duplicate_priority()).sum()
+    Ok(rucksacks.iter().map(|rs| rs.duplicate_priority()).sum())
 }
 
 #[aoc(day3, part2)]
-pub fn solve_part2(input: &str) -> u32 {
-    let groups = parse2(input);
+pub fn solve_part2(input: &str) -> Result<u32, ParseError> {
+    let groups = parse2(input)?;
 
-    groups.iter().map(|group| group.badge_priority()).sum()
+    Ok(groups.iter().map(|group| group.badge_priority()).sum())
 }
 
 #[cfg(test)]
@@ -137,7 +153,7 @@ mod tests {
 		 ttgJtRGJQctTZtZT\n\
 		 CrZsJsPPZsGzwwsLwLmpwMDw\n"
             ),
-            157
+            Ok(157)
         );
     }
 
@@ -152,7 +168,15 @@ mod tests {
 		 ttgJtRGJQctTZtZT\n\
 		 CrZsJsPPZsGzwwsLwLmpwMDw\n"
             ),
-            70
+            Ok(70)
+        );
+    }
+
+    #[test]
+    fn invalid_item() {
+        assert_eq!(
+            solve_part1("vJrwpWtwJgWrhcsFMMfFFhFp\nab1d"),
+            Err(ParseError::new(2, 3, "1d", "item letter"))
         );
     }
 }
@@ -2,13 +2,14 @@ use std::ops::RangeInclusive;
 
 use nom::{
     bytes::complete::tag,
-    character::complete::{newline, u32},
+    character::complete::u32,
     combinator::map,
-    multi::separated_list1,
-    sequence::tuple,
+    sequence::{separated_pair, tuple},
     IResult,
 };
 
+use crate::error::{self, ParseError};
+
 pub struct Pairing((RangeInclusive<u32>, RangeInclusive<u32>));
 
 pub trait FullyContains {
@@ -40,11 +41,7 @@ fn parse_range(input: &str) -> IResult<&str, RangeInclusive<u32>> {
 
 impl Pairing {
     fn parse(input: &str) -> IResult<&str, Self> {
-        let (rest, mut ranges) = separated_list1(tag(","), parse_range)(input)?;
-        assert_eq!(ranges.len(), 2);
-
-        let (second, first) = (ranges.pop().unwrap(), ranges.pop().unwrap());
-        Ok((rest, Self((first, second))))
+        map(separated_pair(parse_range, tag(","), parse_range), Self)(input)
     }
 
     fn fully_contained(&self) -> bool {
@@ -57,10 +54,8 @@ impl Pairing {
 }
 
 #[aoc_generator(day4)]
-pub fn input_generator(input: &str) -> Vec<Pairing> {
-    let (_, pairings) = separated_list1(newline, Pairing::parse)(input).unwrap();
-
-    pairings
+pub fn input_generator(input: &str) -> Result<Vec<Pairing>, ParseError> {
+    error::parse_lines(input, Pairing::parse, "pairing 'a-b,c-d'")
 }
 
 #[aoc(day4, part1)]
@@ -83,14 +78,17 @@ mod tests {
     #[test]
     fn examples_part1() {
         assert_eq!(
-            solve_part1(&input_generator(
-                "2-4,6-8\n\
+            solve_part1(
+                &input_generator(
+                    "2-4,6-8\n\
 		 2-3,4-5\n\
 		 5-7,7-9\n\
 		 2-8,3-7\n\
 		 6-6,4-6\n\
 		 2-6,4-8"
-            )),
+                )
+                .unwrap()
+            ),
             2
         );
     }
@@ -98,15 +96,26 @@ mod tests {
     #[test]
     fn examples_part2() {
         assert_eq!(
-            solve_part2(&input_generator(
-                "2-4,6-8\n\
+            solve_part2(
+                &input_generator(
+                    "2-4,6-8\n\
 		 2-3,4-5\n\
 		 5-7,7-9\n\
 		 2-8,3-7\n\
 		 6-6,4-6\n\
 		 2-6,4-8"
-            )),
+                )
+                .unwrap()
+            ),
             4
         );
     }
+
+    #[test]
+    fn invalid_pairing() {
+        assert_eq!(
+            input_generator("2-4,6-8\n2-3;4-5").err(),
+            Some(ParseError::new(2, 4, ";4-5", "pairing 'a-b,c-d'"))
+        );
+    }
 }
@@ -1,3 +1,5 @@
+use crate::error::ParseError;
+
 struct Crates(Vec<Vec<char>>);
 
 struct Move {
@@ -11,73 +13,149 @@ struct Puzzle {
     moves: Vec<Move>,
 }
 
-impl From<&str> for Puzzle {
-    fn from(input: &str) -> Self {
-        let parts: Vec<_> = input.split("\n\n").collect();
-
-        assert_eq!(parts.len(), 2);
+impl TryFrom<&str> for Puzzle {
+    type Error = ParseError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        let (crates, moves) = input.split_once("\n\n").ok_or_else(|| {
+            ParseError::at(input, &input[input.len()..], "blank line before moves")
+        })?;
+
+        let moves_offset = crates.lines().count() + 1;
+        let crates = Crates::try_from(crates)?;
+
+        // both parts move the same number of crates between stacks, so one
+        // check of the heights covers them
+        let mut heights: Vec<_> = crates.0.iter().map(Vec::len).collect();
+        let moves = moves
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                let mv = Move::parse(line, crates.0.len())
+                    .map_err(|e| e.shift_lines(moves_offset + i))?;
+
+                if mv.qty > heights[mv.from] {
+                    let qty = line.split_whitespace().nth(1).unwrap();
+                    let expected = format!(
+                        "at most {} crates to move from stack {}",
+                        heights[mv.from],
+                        mv.from + 1
+                    );
+                    return Err(ParseError::at(line, qty, expected).shift_lines(moves_offset + i));
+                }
 
-        let crates = Crates::from(parts[0]);
-        let moves = parts[1].lines().map(Move::from).collect();
+                heights[mv.from] -= mv.qty;
+                heights[mv.to] += mv.qty;
+                Ok(mv)
+            })
+            .collect::<Result<_, _>>()?;
 
-        Self { crates, moves }
+        Ok(Self { crates, moves })
     }
 }
 
-impl From<&str> for Crates {
-    fn from(input: &str) -> Self {
+impl TryFrom<&str> for Crates {
+    type Error = ParseError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
         let mut lines: Vec<_> = input.lines().collect();
 
-        // ignore the labels at the end
-        lines.pop();
+        // the labels at the end only tell us how many stacks there are
+        let labels = lines
+            .pop()
+            .ok_or_else(|| ParseError::new(1, 1, "", "stack labels"))?;
+
+        for (i, label) in labels.split_whitespace().enumerate() {
+            if label.parse() != Ok(i + 1) {
+                return Err(
+                    ParseError::at(labels, label, format!("stack label {}", i + 1))
+                        .shift_lines(lines.len()),
+                );
+            }
+        }
 
-        let n = Crates::num_crates(lines[0].len());
+        let n = labels.split_whitespace().count();
         let mut stacks = Vec::with_capacity(n);
 
         for _ in 0..n {
             stacks.push(Vec::new());
         }
 
-        for &line in lines.iter().rev() {
-            let mut chars = line.chars();
-
-            for stack in stacks.iter_mut() {
-                chars.next(); // '['
-
-                // letter?
-                let ch = chars.next().unwrap();
-                if ch.is_alphabetic() {
-                    stack.push(ch);
+        for (i, &line) in lines.iter().enumerate().rev() {
+            let chars: Vec<_> = line.chars().collect();
+
+            for (j, stack) in stacks.iter_mut().enumerate() {
+                let slot: Vec<_> = chars.iter().skip(j * 4).take(3).copied().collect();
+
+                match slot[..] {
+                    ['[', ch, ']'] if ch.is_alphabetic() => stack.push(ch),
+                    [] | [' '] | [' ', ' '] | [' ', ' ', ' '] => {}
+                    _ => {
+                        return Err(ParseError::new(
+                            i + 1,
+                            j * 4 + 1,
+                            &slot.iter().collect::<String>(),
+                            "crate '[X]' or blank",
+                        ))
+                    }
                 }
+            }
 
-                chars.next(); // ']'
-                chars.next(); // ' '
+            if chars.len() > n * 4 {
+                return Err(ParseError::new(
+                    i + 1,
+                    n * 4 + 1,
+                    &chars[n * 4..].iter().collect::<String>(),
+                    "end of line",
+                ));
             }
         }
 
-        Self(stacks)
+        Ok(Self(stacks))
     }
 }
 
-impl From<&str> for Move {
-    fn from(input: &str) -> Self {
+impl Move {
+    fn parse(input: &str, num_stacks: usize) -> Result<Self, ParseError> {
         let parts: Vec<_> = input.split_whitespace().collect();
+        let end = &input[input.len()..];
 
-        assert_eq!(parts.len(), 6);
+        for (i, word) in [(0, "move"), (2, "from"), (4, "to")] {
+            if parts.get(i) != Some(&word) {
+                let found = parts.get(i).copied().unwrap_or(end);
+                return Err(ParseError::at(input, found, format!("'{word}'")));
+            }
+        }
+
+        let number = |i: usize, expected: &str| {
+            let part = parts.get(i).copied().unwrap_or(end);
+            part.parse::<usize>()
+                .map_err(|_| ParseError::at(input, part, expected))
+                .map(|n| (part, n))
+        };
+
+        let (_, qty) = number(1, "crate count")?;
 
-        let qty = parts[1].parse().unwrap();
-        let from = (parts[3].parse::<isize>().unwrap() - 1) as usize;
-        let to = (parts[5].parse::<isize>().unwrap() - 1) as usize;
+        let stack = |i: usize| {
+            let expected = format!("stack number between 1 and {num_stacks}");
+            match number(i, &expected)? {
+                (_, n) if (1..=num_stacks).contains(&n) => Ok(n - 1),
+                (part, _) => Err(ParseError::at(input, part, expected)),
+            }
+        };
+
+        let from = stack(3)?;
+        let to = stack(5)?;
+
+        if let Some(&extra) = parts.get(6) {
+            return Err(ParseError::at(input, extra, "end of line"));
+        }
 
-        Self { qty, from, to }
+        Ok(Self { qty, from, to })
     }
 }
 
 impl Crates {
-    fn num_crates(len: usize) -> usize {
-        (len + 1) / 4
-    }
-
     fn top_crates(&self) -> Vec<char> {
         let mut vec = Vec::new();
 
@@ -90,6 +168,7 @@ impl Crates {
         vec
     }
 
+    // Puzzle::try_from checked every move has enough crates to take
     fn mv(&mut self, from: usize, to: usize) {
         let tmp = (self.0)[from].pop().unwrap();
         (self.0)[to].push(tmp);
@@ -129,17 +208,17 @@ impl Puzzle {
 }
 
 #[aoc(day5, part1)]
-pub fn solve_part1(input: &str) -> String {
-    let mut puzzle = Puzzle::from(input);
+pub fn solve_part1(input: &str) -> Result<String, ParseError> {
+    let mut puzzle = Puzzle::try_from(input)?;
     puzzle.process();
-    puzzle.top_crates().into_iter().collect()
+    Ok(puzzle.top_crates().into_iter().collect())
 }
 
 #[aoc(day5, part2)]
-pub fn solve_part2(input: &str) -> String {
-    let mut puzzle = Puzzle::from(input);
+pub fn solve_part2(input: &str) -> Result<String, ParseError> {
+    let mut puzzle = Puzzle::try_from(input)?;
     puzzle.process2();
-    puzzle.top_crates().into_iter().collect()
+    Ok(puzzle.top_crates().into_iter().collect())
 }
 
 #[cfg(test)]
@@ -160,7 +239,7 @@ mod tests {
 		 move 2 from 2 to 1\n\
 		 move 1 from 1 to 2"
             ),
-            "CMZ"
+            Ok("CMZ".to_string())
         );
     }
 
@@ -178,7 +257,36 @@ mod tests {
 		 move 2 from 2 to 1\n\
 		 move 1 from 1 to 2"
             ),
-            "MCD"
+            Ok("MCD".to_string())
+        );
+    }
+
+    #[test]
+    fn invalid_stack() {
+        assert_eq!(
+            solve_part1(
+                "[Z] [M] [P]\n\
+		  1   2   3 \n\
+		 \n\
+		 move 1 from 2 to 1\n\
+		 move 3 from 1 to 4"
+            ),
+            Err(ParseError::new(5, 18, "4", "stack number between 1 and 3"))
+        );
+    }
+
+    #[test]
+    fn too_many_crates() {
+        let input = "[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\nmove 3 from 1 to 3";
+        assert_eq!(
+            solve_part1(input),
+            Err(ParseError::new(
+                5,
+                6,
+                "3 from 1 to 3",
+                "at most 2 crates to move from stack 1"
+            ))
         );
+        assert_eq!(solve_part2(input), solve_part1(input));
     }
 }
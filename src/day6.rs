@@ -1,24 +1,31 @@
 use std::collections::HashSet;
 
-fn solve(input: &str, size: usize) -> usize {
+use crate::error::ParseError;
+
+fn solve(input: &str, size: usize) -> Result<usize, ParseError> {
     let chars = input.chars().collect::<Vec<_>>();
 
     chars
         .windows(size)
         .enumerate()
         .find(|(_i, win)| win.len() == win.iter().copied().collect::<HashSet<char>>().len())
-        .unwrap()
-        .0
-        + size
+        .map(|(i, _win)| i + size)
+        .ok_or_else(|| {
+            ParseError::at(
+                input,
+                &input[input.len()..],
+                format!("marker of {size} distinct characters"),
+            )
+        })
 }
 
 #[aoc(day6, part1)]
-pub fn solve_part1(input: &str) -> usize {
+pub fn solve_part1(input: &str) -> Result<usize, ParseError> {
     solve(input, 4)
 }
 
 #[aoc(day6, part2)]
-pub fn solve_part2(input: &str) -> usize {
+pub fn solve_part2(input: &str) -> Result<usize, ParseError> {
     solve(input, 14)
 }
 
@@ -28,19 +35,27 @@ mod tests {
 
     #[test]
     fn examples_part1() {
-        assert_eq!(solve_part1("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), 7);
-        assert_eq!(solve_part1("bvwbjplbgvbhsrlpgdmjqwftvncz"), 5);
-        assert_eq!(solve_part1("nppdvjthqldpwncqszvftbrmjlhg"), 6);
-        assert_eq!(solve_part1("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), 10);
-        assert_eq!(solve_part1("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), 11);
+        assert_eq!(solve_part1("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), Ok(7));
+        assert_eq!(solve_part1("bvwbjplbgvbhsrlpgdmjqwftvncz"), Ok(5));
+        assert_eq!(solve_part1("nppdvjthqldpwncqszvftbrmjlhg"), Ok(6));
+        assert_eq!(solve_part1("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), Ok(10));
+        assert_eq!(solve_part1("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), Ok(11));
     }
 
     #[test]
     fn examples_part2() {
-        assert_eq!(solve_part2("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), 19);
-        assert_eq!(solve_part2("bvwbjplbgvbhsrlpgdmjqwftvncz"), 23);
-        assert_eq!(solve_part2("nppdvjthqldpwncqszvftbrmjlhg"), 23);
-        assert_eq!(solve_part2("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), 29);
-        assert_eq!(solve_part2("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), 26);
+        assert_eq!(solve_part2("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), Ok(19));
+        assert_eq!(solve_part2("bvwbjplbgvbhsrlpgdmjqwftvncz"), Ok(23));
+        assert_eq!(solve_part2("nppdvjthqldpwncqszvftbrmjlhg"), Ok(23));
+        assert_eq!(solve_part2("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), Ok(29));
+        assert_eq!(solve_part2("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), Ok(26));
+    }
+
+    #[test]
+    fn no_marker() {
+        assert_eq!(
+            solve_part1("abab"),
+            Err(ParseError::new(1, 5, "", "marker of 4 distinct characters"))
+        );
     }
 }
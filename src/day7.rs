@@ -1,6 +1,8 @@
 use std::cell::RefCell;
 use std::rc::{Rc, Weak};
 
+use crate::error::ParseError;
+
 pub enum File {
     Directory(Directory),
     Regular(Regular),
@@ -93,7 +95,7 @@ impl Regular {
 }
 
 #[aoc_generator(day7)]
-pub fn input_generator(input: &str) -> Rc<RefCell<File>> {
+pub fn input_generator(input: &str) -> Result<Rc<RefCell<File>>, ParseError> {
     let files = Vec::new();
     let parent = None;
 
@@ -104,34 +106,44 @@ pub fn input_generator(input: &str) -> Rc<RefCell<File>> {
         parent,
     })));
 
+    let mut lines = input.lines();
+
+    if lines.next() != Some("$ cd /") {
+        return Err(ParseError::at(input, input, "'$ cd /'"));
+    }
+
     let mut in_ls = false;
     let mut cur = Rc::clone(&root);
 
-    for line in input.lines().skip(1) {
+    for (i, line) in lines.enumerate() {
+        let err = |part: Option<&&str>, expected: &str| {
+            ParseError::at(line, part.copied().unwrap_or(&line[line.len()..]), expected)
+                .shift_lines(i + 1)
+        };
+
         let parts = line.split_whitespace().collect::<Vec<_>>();
 
-        match parts[0] {
-            "$" => {
+        match parts.first().copied() {
+            Some("$") => {
                 if in_ls {
                     in_ls = false;
                 }
 
-                match parts[1] {
-                    "cd" => match parts[2] {
-                        ".." => {
+                match parts.get(1).copied() {
+                    Some("cd") => match parts.get(2).copied() {
+                        Some("..") => {
                             let next_cur = cur
                                 .borrow_mut()
                                 .dir()
                                 .parent
                                 .as_ref()
-                                .unwrap()
-                                .upgrade()
-                                .unwrap();
+                                .and_then(Weak::upgrade)
+                                .ok_or_else(|| err(parts.get(2), "subdirectory of '/'"))?;
 
                             cur = next_cur;
                         }
 
-                        d => {
+                        Some(d) => {
                             let subdir = Rc::new(RefCell::new(File::Directory(Directory {
                                 _name: d.to_string(),
                                 files: Vec::new(),
@@ -143,28 +155,34 @@ pub fn input_generator(input: &str) -> Rc<RefCell<File>> {
                             cur.borrow_mut().dir().files.push(subdir);
                             cur = next_cur;
                         }
+
+                        None => return Err(err(None, "directory name")),
                     },
-                    "ls" => {
+                    Some("ls") => {
                         in_ls = true;
                     }
-                    other => {
-                        panic!("Unknown command {other}")
-                    }
+                    _ => return Err(err(parts.get(1), "command 'cd' or 'ls'")),
                 }
             }
-            "dir" => { /*nop, we'll catch it when we `cd` */ }
-            n => {
-                let size = n.parse::<usize>().unwrap();
-                let _name = parts[1].to_string();
+            Some("dir") if in_ls => { /*nop, we'll catch it when we `cd` */ }
+            Some(n) if in_ls => {
+                let size = n
+                    .parse::<usize>()
+                    .map_err(|_| err(parts.first(), "file size or 'dir'"))?;
+                let _name = parts
+                    .get(1)
+                    .ok_or_else(|| err(None, "file name"))?
+                    .to_string();
 
                 let this = Rc::new(RefCell::new(File::Regular(Regular { _name, size })));
 
                 cur.borrow_mut().dir().files.push(this);
             }
+            _ => return Err(err(parts.first(), "'$'")),
         }
     }
 
-    root
+    Ok(root)
 }
 
 #[aoc(day7, part1)]
@@ -195,8 +213,9 @@ mod tests {
     #[test]
     fn examples_part1() {
         assert_eq!(
-            solve_part1(&input_generator(
-                "$ cd /\n\
+            solve_part1(
+                &input_generator(
+                    "$ cd /\n\
 		 $ ls\n\
 		 dir a\n\
 		 14848514 b.txt\n\
@@ -219,7 +238,9 @@ mod tests {
 		 8033020 d.log\n\
 		 5626152 d.ext\n\
 		 7214296 k"
-            )),
+                )
+                .unwrap()
+            ),
             95437
         );
     }
@@ -227,8 +248,9 @@ mod tests {
     #[test]
     fn examples_part2() {
         assert_eq!(
-            solve_part2(&input_generator(
-                "$ cd /\n\
+            solve_part2(
+                &input_generator(
+                    "$ cd /\n\
 		 $ ls\n\
 		 dir a\n\
 		 14848514 b.txt\n\
@@ -251,8 +273,18 @@ mod tests {
 		 8033020 d.log\n\
 		 5626152 d.ext\n\
 		 7214296 k"
-            )),
+                )
+                .unwrap()
+            ),
             24933642
         );
     }
+
+    #[test]
+    fn invalid_command() {
+        assert_eq!(
+            input_generator("$ cd /\n$ ls\n1 a\n$ rm a").err(),
+            Some(ParseError::new(4, 3, "rm a", "command 'cd' or 'ls'"))
+        );
+    }
 }
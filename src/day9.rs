@@ -1,3 +1,6 @@
+use crate::error::ParseError;
+use crate::point::Point;
+
 #[derive(Debug)]
 enum Direction {
     Up,
@@ -6,14 +9,16 @@ enum Direction {
     Right,
 }
 
-impl From<&str> for Direction {
-    fn from(s: &str) -> Self {
+impl TryFrom<&str> for Direction {
+    type Error = ();
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
         match s {
-            "U" => Direction::Up,
-            "D" => Direction::Down,
-            "L" => Direction::Left,
-            "R" => Direction::Right,
-            _ => panic!("Invalid direction {}", s),
+            "U" => Ok(Direction::Up),
+            "D" => Ok(Direction::Down),
+            "L" => Ok(Direction::Left),
+            "R" => Ok(Direction::Right),
+            _ => Err(()),
         }
     }
 }
@@ -24,12 +29,26 @@ pub struct Move {
     n: usize,
 }
 
-#[derive(Default, Clone, Copy, Eq, PartialEq, Hash, Debug)]
-struct Position {
-    x: isize,
-    y: isize,
+impl TryFrom<&str> for Move {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let (d, n) = s
+            .split_once(' ')
+            .ok_or_else(|| ParseError::at(s, s, "'<direction> <steps>'"))?;
+
+        let d = Direction::try_from(d)
+            .map_err(|_| ParseError::at(s, d, "direction 'U', 'D', 'L' or 'R'"))?;
+        let n = n
+            .parse()
+            .map_err(|_| ParseError::at(s, n, "number of steps"))?;
+
+        Ok(Move { d, n })
+    }
 }
 
+type Position = Point<isize>;
+
 struct State<const N: usize> {
     nodes: [Position; N],
     visited: std::collections::HashSet<Position>,
@@ -131,15 +150,11 @@ impl<const N: usize> State<N> {
 }
 
 #[aoc_generator(day9)]
-pub fn input_generator(input: &str) -> Vec<Move> {
+pub fn input_generator(input: &str) -> Result<Vec<Move>, ParseError> {
     input
         .lines()
-        .map(|l| {
-            let mut split = l.split(' ');
-            let d = split.next().unwrap().into();
-            let n = split.next().unwrap().parse().unwrap();
-            Move { d, n }
-        })
+        .enumerate()
+        .map(|(i, l)| Move::try_from(l).map_err(|e| e.shift_lines(i)))
         .collect()
 }
 
@@ -232,8 +247,9 @@ mod tests {
     #[test]
     fn examples_part1() {
         assert_eq!(
-            solve_part1(&input_generator(
-                "R 4\n\
+            solve_part1(
+                &input_generator(
+                    "R 4\n\
 		 U 4\n\
 		 L 3\n\
 		 D 1\n\
@@ -241,7 +257,9 @@ mod tests {
 		 D 1\n\
 		 L 5\n\
 		 R 2"
-            )),
+                )
+                .unwrap()
+            ),
             13
         );
     }
@@ -249,8 +267,9 @@ mod tests {
     #[test]
     fn examples_part2() {
         assert_eq!(
-            solve_part2(&input_generator(
-                "R 4\n\
+            solve_part2(
+                &input_generator(
+                    "R 4\n\
 		 U 4\n\
 		 L 3\n\
 		 D 1\n\
@@ -258,12 +277,15 @@ mod tests {
 		 D 1\n\
 		 L 5\n\
 		 R 2"
-            )),
+                )
+                .unwrap()
+            ),
             1
         );
         assert_eq!(
-            solve_part2(&input_generator(
-                "R 5\n\
+            solve_part2(
+                &input_generator(
+                    "R 5\n\
 		 U 8\n\
 		 L 8\n\
 		 D 3\n\
@@ -271,8 +293,27 @@ mod tests {
 		 D 10\n\
 		 L 25\n\
 		 U 20"
-            )),
+                )
+                .unwrap()
+            ),
             36
         );
     }
+
+    #[test]
+    fn invalid_move() {
+        assert_eq!(
+            input_generator("R 4\nX 4").err(),
+            Some(ParseError::new(
+                2,
+                1,
+                "X 4",
+                "direction 'U', 'D', 'L' or 'R'"
+            ))
+        );
+        assert_eq!(
+            input_generator("R 4\nU four").err(),
+            Some(ParseError::new(2, 3, "four", "number of steps"))
+        );
+    }
 }
@@ -0,0 +1,178 @@
+use std::fmt;
+
+/// An error produced while parsing puzzle input.
+///
+/// Lines and columns are 1-based and columns count characters, not bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, text: &str, expected: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            text: text.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Builds an error for `offending`, which must be a subslice of
+    /// `input`. The line and column are computed relative to the start of
+    /// `input`, and the reported text runs to the end of the offending
+    /// line.
+    pub fn at(input: &str, offending: &str, expected: impl Into<String>) -> Self {
+        let offset = (offending.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+        let offset = if offset <= input.len() && input.is_char_boundary(offset) {
+            offset
+        } else {
+            0
+        };
+
+        let before = &input[..offset];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let column = input[line_start..offset].chars().count() + 1;
+        let text = input[offset..].lines().next().unwrap_or("");
+
+        Self::new(line, column, text, expected)
+    }
+
+    /// Builds an error from a failed nom parse of `input`.
+    pub fn from_nom(
+        input: &str,
+        err: nom::Err<nom::error::Error<&str>>,
+        expected: impl Into<String>,
+    ) -> Self {
+        match err {
+            nom::Err::Error(e) | nom::Err::Failure(e) => Self::at(input, e.input, expected),
+            nom::Err::Incomplete(_) => Self::at(input, &input[input.len()..], expected),
+        }
+    }
+
+    /// Moves the error down by `lines`, for errors produced while parsing
+    /// a chunk that doesn't start on the first line of the input.
+    pub fn shift_lines(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+
+        if self.text.is_empty() {
+            write!(f, "end of line")
+        } else {
+            write!(f, "'{}'", self.text)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Finishes a nom parse of `input`, failing if it errored or left anything
+/// other than trailing whitespace unconsumed.
+pub fn finish<'a, T>(
+    input: &'a str,
+    result: nom::IResult<&'a str, T>,
+    expected: &str,
+) -> Result<T, ParseError> {
+    match result {
+        Ok((rest, value)) if rest.trim().is_empty() => Ok(value),
+        Ok((rest, _)) => Err(ParseError::at(input, rest.trim_start(), expected)),
+        Err(e) => Err(ParseError::from_nom(input, e, expected)),
+    }
+}
+
+/// Runs a nom `parser` over every line of `input`, requiring each line to
+/// be consumed completely.
+pub fn parse_lines<'a, T>(
+    input: &'a str,
+    mut parser: impl FnMut(&'a str) -> nom::IResult<&'a str, T>,
+    expected: &str,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| finish(line, parser(line), expected).map_err(|e| e.shift_lines(i)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn at_position() {
+        let input = "abc\ndef\nghi";
+
+        assert_eq!(
+            ParseError::at(input, &input[0..], "x"),
+            ParseError::new(1, 1, "abc", "x")
+        );
+        assert_eq!(
+            ParseError::at(input, &input[5..], "x"),
+            ParseError::new(2, 2, "ef", "x")
+        );
+        assert_eq!(
+            ParseError::at(input, &input[11..], "x"),
+            ParseError::new(3, 4, "", "x")
+        );
+    }
+
+    #[test]
+    fn shift_lines() {
+        let e = ParseError::new(1, 3, "foo", "bar").shift_lines(4);
+        assert_eq!(e.line, 5);
+        assert_eq!(e.column, 3);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            ParseError::new(2, 5, "zz", "digit").to_string(),
+            "line 2, column 5: expected digit, found 'zz'"
+        );
+        assert_eq!(
+            ParseError::new(1, 1, "", "digit").to_string(),
+            "line 1, column 1: expected digit, found end of line"
+        );
+    }
+
+    #[test]
+    fn finish_trailing() {
+        use nom::character::complete::u32;
+
+        let input = "12 34";
+        assert_eq!(
+            finish(input, u32(input), "number"),
+            Err(ParseError::new(1, 4, "34", "number"))
+        );
+        assert_eq!(finish("12\n", u32("12\n"), "number"), Ok(12));
+    }
+
+    #[test]
+    fn parse_lines_position() {
+        use nom::character::complete::u32;
+
+        assert_eq!(parse_lines("1\n2\n3", u32, "number"), Ok(vec![1, 2, 3]));
+        assert_eq!(
+            parse_lines("1\n2\n3x", u32, "number"),
+            Err(ParseError::new(3, 2, "x", "number"))
+        );
+        assert_eq!(
+            parse_lines("1\n\n3", u32, "number"),
+            Err(ParseError::new(2, 1, "", "number"))
+        );
+    }
+}
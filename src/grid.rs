@@ -0,0 +1,361 @@
+use std::ops::{Index, IndexMut};
+
+use crate::error::ParseError;
+
+/// A position in a [`Grid`], as `(x, y)` with the origin at the top left.
+pub type Pos = (usize, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    /// The four orthogonal directions, in reading order of the cells they
+    /// point to.
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Left,
+        Direction::Right,
+        Direction::Down,
+    ];
+
+    /// All eight directions, in reading order of the cells they point to.
+    pub const ALL: [Direction; 8] = [
+        Direction::UpLeft,
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Left,
+        Direction::Right,
+        Direction::DownLeft,
+        Direction::Down,
+        Direction::DownRight,
+    ];
+
+    pub fn delta(&self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+            Direction::UpLeft => (-1, -1),
+            Direction::UpRight => (1, -1),
+            Direction::DownLeft => (-1, 1),
+            Direction::DownRight => (1, 1),
+        }
+    }
+}
+
+/// A dense, row-major 2D grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T: Clone> Grid<T> {
+    /// Panics if `width` is zero.
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        assert!(width > 0, "grid width must be positive");
+
+        Self {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from row-major `cells`. Panics if `cells` doesn't
+    /// divide evenly into rows of `width`.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        assert!(width > 0 && cells.len().is_multiple_of(width));
+
+        let height = cells.len() / width;
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Parses a rectangular character map, converting each character with
+    /// `cell`. Characters for which `cell` returns `None` are reported as
+    /// errors expecting `expected`.
+    pub fn parse(
+        input: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+        expected: &str,
+    ) -> Result<Self, ParseError> {
+        let width = input.lines().next().map_or(0, |l| l.chars().count());
+        let mut cells = Vec::new();
+        let mut height = 0;
+
+        for (i, line) in input.lines().enumerate() {
+            for (j, c) in line.char_indices() {
+                let value = cell(c)
+                    .ok_or_else(|| ParseError::at(line, &line[j..], expected).shift_lines(i))?;
+                cells.push(value);
+            }
+
+            if line.chars().count() != width {
+                return Err(ParseError::new(
+                    i + 1,
+                    1,
+                    line,
+                    format!("row of {width} cells"),
+                ));
+            }
+
+            height += 1;
+        }
+
+        if width == 0 {
+            return Err(ParseError::new(1, 1, "", expected));
+        }
+
+        Ok(Self {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The cells in row-major order, for callers that address them by
+    /// offset rather than position.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// The offset of `pos` into the row-major cell storage.
+    pub fn index_of(&self, pos: Pos) -> Option<usize> {
+        self.contains(pos).then_some(pos.1 * self.width + pos.0)
+    }
+
+    /// The position of the cell at row-major offset `index`.
+    pub fn pos_of(&self, index: usize) -> Pos {
+        (index % self.width, index / self.width)
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// The position one step from `pos` in `dir`, if it's still inside the
+    /// grid.
+    pub fn step(&self, (x, y): Pos, dir: Direction) -> Option<Pos> {
+        let (dx, dy) = dir.delta();
+        let next = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.contains(next).then_some(next)
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i % width, i / width))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Panics if `y` is outside the grid.
+    pub fn row(&self, y: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(
+            y < self.height,
+            "row {y} outside grid of height {}",
+            self.height
+        );
+        self.cells[y * self.width..(y + 1) * self.width].iter()
+    }
+
+    /// Panics if `x` is outside the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            x < self.width,
+            "column {x} outside grid of width {}",
+            self.width
+        );
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// Walks from `pos` (exclusive) in `dir` until the edge of the grid.
+    pub fn ray(&self, pos: Pos, dir: Direction) -> impl Iterator<Item = (Pos, &T)> {
+        std::iter::successors(self.step(pos, dir), move |&p| self.step(p, dir))
+            .map(|p| (p, &self[p]))
+    }
+
+    /// The up to four orthogonally adjacent positions, in reading order.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction::ORTHOGONAL
+            .into_iter()
+            .filter_map(move |d| self.step(pos, d))
+    }
+
+    /// The up to eight adjacent positions, including diagonals, in reading
+    /// order.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |d| self.step(pos, d))
+    }
+
+    /// Renders the grid as text, one line per row, converting each cell with
+    /// `cell`.
+    pub fn render(&self, mut cell: impl FnMut(&T) -> char) -> String {
+        let mut output = String::with_capacity((self.width + 1) * self.height);
+
+        for y in 0..self.height {
+            output.extend(self.row(y).map(&mut cell));
+            output.push('\n');
+        }
+
+        // remove one trailing newline
+        output.pop();
+
+        output
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos).expect("position outside grid")
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        self.get_mut(pos).expect("position outside grid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Grid<u32> {
+        Grid::parse(input, |c| c.to_digit(10), "digit").unwrap()
+    }
+
+    #[test]
+    fn parse_and_render() {
+        let grid = digits("123\n456");
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(
+            grid.render(|&d| char::from_digit(d, 10).unwrap()),
+            "123\n456"
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Grid::parse("12\n3x", |c| c.to_digit(10), "digit"),
+            Err(ParseError::new(2, 2, "x", "digit"))
+        );
+        assert_eq!(
+            Grid::parse("12\n345", |c| c.to_digit(10), "digit"),
+            Err(ParseError::new(2, 1, "345", "row of 2 cells"))
+        );
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = digits("123\n456\n789");
+        assert_eq!(grid.row(1).copied().collect::<Vec<_>>(), vec![4, 5, 6]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![3, 6, 9]);
+    }
+
+    #[test]
+    #[should_panic(expected = "column 4 outside grid of width 3")]
+    fn column_outside() {
+        digits("123\n456\n789").column(4).count();
+    }
+
+    #[test]
+    #[should_panic(expected = "row 3 outside grid of height 3")]
+    fn row_outside() {
+        digits("123\n456\n789").row(3).count();
+    }
+
+    #[test]
+    #[should_panic(expected = "grid width must be positive")]
+    fn zero_width() {
+        Grid::new(0, 3, 0);
+    }
+
+    #[test]
+    fn rays() {
+        let grid = digits("123\n456\n789");
+        let ray = |pos, dir| grid.ray(pos, dir).map(|(_, &d)| d).collect::<Vec<_>>();
+
+        assert_eq!(ray((1, 1), Direction::Up), vec![2]);
+        assert_eq!(ray((2, 2), Direction::Left), vec![8, 7]);
+        assert_eq!(ray((0, 0), Direction::DownRight), vec![5, 9]);
+        assert_eq!(ray((0, 0), Direction::Up), vec![]);
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = Grid::new(3, 3, ());
+        assert_eq!(
+            grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(
+            grid.neighbors4((1, 1)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1), (2, 1), (1, 2)]
+        );
+        assert_eq!(
+            grid.neighbors8((2, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (1, 1), (2, 1)]
+        );
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn index_round_trip() {
+        let grid = Grid::new(4, 3, 0);
+        for pos in grid.positions() {
+            assert_eq!(grid.pos_of(grid.index_of(pos).unwrap()), pos);
+        }
+        assert_eq!(grid.index_of((4, 0)), None);
+    }
+}
@@ -0,0 +1,181 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// A point on a 2D plane. Days pick whichever coordinate type suits them;
+/// for grid-backed maps see [`crate::grid::Pos`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A point in 3D space, e.g. one unit cube of a voxel model.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+// |a - b| without needing signed or `abs`
+fn distance<T: Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point<T> {
+    pub fn manhattan_distance(&self, other: &Self) -> T {
+        distance(self.x, other.x) + distance(self.y, other.y)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point3<T> {
+    pub fn manhattan_distance(&self, other: &Self) -> T {
+        distance(self.x, other.x) + distance(self.y, other.y) + distance(self.z, other.z)
+    }
+
+    /// The smallest and largest value of each coordinate across `points`,
+    /// as two corners of a bounding box. `None` if there are no points.
+    pub fn bounds(points: impl IntoIterator<Item = Self>) -> Option<(Self, Self)> {
+        points.into_iter().fold(None, |acc, p| match acc {
+            None => Some((p, p)),
+            Some((min, max)) => Some((
+                Self::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z)),
+                Self::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z)),
+            )),
+        })
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Mul<Output = T>> Point3<T> {
+    pub fn dot(&self, other: &Self) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Sub<Output = T> + From<u8>> Point3<T> {
+    /// The six points sharing a face with this one. Unsigned coordinates
+    /// overflow on the zero planes, so callers should keep a margin there.
+    pub fn neighbors6(self) -> [Self; 6] {
+        let one = T::from(1);
+        let Self { x, y, z } = self;
+
+        [
+            Self::new(x - one, y, z),
+            Self::new(x + one, y, z),
+            Self::new(x, y - one, z),
+            Self::new(x, y + one, z),
+            Self::new(x, y, z - one),
+            Self::new(x, y, z + one),
+        ]
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point3<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Self::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manhattan_distance() {
+        assert_eq!(Point::new(8, 7).manhattan_distance(&Point::new(2, 10)), 9);
+        assert_eq!(
+            Point::<usize>::new(1, 5).manhattan_distance(&Point::new(4, 2)),
+            6
+        );
+        assert_eq!(
+            Point3::new(1, 2, 3).manhattan_distance(&Point3::new(-1, 2, 6)),
+            5
+        );
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(Point::new(1, 2) + Point::new(3, -4), Point::new(4, -2));
+        assert_eq!(
+            Point3::new(1, 2, 3) - Point3::new(1, 1, 1),
+            Point3::new(0, 1, 2)
+        );
+        assert_eq!(-Point3::new(1, -2, 0) * 3, Point3::new(-3, 6, 0));
+        assert_eq!(Point3::new(1, 2, 3).dot(&Point3::new(4, -5, 6)), 12);
+    }
+
+    #[test]
+    fn neighbors6() {
+        let neighbors = Point3::new(0i32, 0, 0).neighbors6();
+        assert_eq!(neighbors.len(), 6);
+        assert!(neighbors
+            .iter()
+            .all(|n| n.manhattan_distance(&Point3::new(0, 0, 0)) == 1));
+    }
+
+    #[test]
+    fn bounds() {
+        assert_eq!(Point3::<i32>::bounds([]), None);
+        assert_eq!(
+            Point3::bounds([Point3::new(1, 5, 2), Point3::new(3, 0, 2)]),
+            Some((Point3::new(1, 0, 2), Point3::new(3, 5, 2)))
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use aoc_runner::{ArcStr, Runner};
+
+// the factory traits are generated by `aoc_lib!`
+use crate::aoc_factory::*;
+
+/// Generates the input for a solution and returns a runner ready to solve it.
+pub type RunnerFactory = fn(ArcStr) -> Result<Box<dyn Runner>, Box<dyn Error>>;
+
+/// A registered `#[aoc(...)]` solution.
+pub struct Solution {
+    pub day: u32,
+    pub part: u32,
+    pub name: Option<&'static str>,
+    pub factory: RunnerFactory,
+}
+
+impl Solution {
+    const fn new(day: u32, part: u32, name: Option<&'static str>, factory: RunnerFactory) -> Self {
+        Self {
+            day,
+            part,
+            name,
+            factory,
+        }
+    }
+
+    /// Generates the input for this solution from the raw puzzle text.
+    pub fn generate(&self, input: &str) -> Result<Box<dyn Runner>, Box<dyn Error>> {
+        (self.factory)(ArcStr::from(input))
+    }
+
+    /// Generates the input and solves it in one go.
+    pub fn solve(&self, input: &str) -> Result<String, Box<dyn Error>> {
+        Ok(self.generate(input)?.try_run()?.to_string())
+    }
+
+    pub fn label(&self) -> String {
+        match self.name {
+            Some(name) => format!("Day {} - Part {} - {}", self.day, self.part, name),
+            None => format!("Day {} - Part {}", self.day, self.part),
+        }
+    }
+}
+
+/// Every solution in the crate, ordered by day, part and name.
+pub static SOLUTIONS: &[Solution] = &[
+    Solution::new(1, 1, None, Factory::day1_part1),
+    Solution::new(1, 2, None, Factory::day1_part2),
+    Solution::new(2, 1, None, Factory::day2_part1),
+    Solution::new(2, 2, None, Factory::day2_part2),
+    Solution::new(3, 1, None, Factory::day3_part1),
+    Solution::new(3, 2, None, Factory::day3_part2),
+    Solution::new(4, 1, None, Factory::day4_part1),
+    Solution::new(4, 2, None, Factory::day4_part2),
+    Solution::new(5, 1, None, Factory::day5_part1),
+    Solution::new(5, 2, None, Factory::day5_part2),
+    Solution::new(6, 1, None, Factory::day6_part1),
+    Solution::new(6, 2, None, Factory::day6_part2),
+    Solution::new(7, 1, None, Factory::day7_part1),
+    Solution::new(7, 2, None, Factory::day7_part2),
+    Solution::new(8, 1, None, Factory::day8_part1),
+    Solution::new(8, 2, None, Factory::day8_part2),
+    Solution::new(9, 1, None, Factory::day9_part1),
+    Solution::new(9, 2, None, Factory::day9_part2),
+    Solution::new(10, 1, None, Factory::day10_part1),
+    Solution::new(10, 2, None, Factory::day10_part2),
+    Solution::new(11, 1, None, Factory::day11_part1),
+    Solution::new(11, 2, None, Factory::day11_part2),
+    Solution::new(12, 1, None, Factory::day12_part1),
+    Solution::new(12, 2, None, Factory::day12_part2),
+    Solution::new(13, 1, None, Factory::day13_part1),
+    Solution::new(13, 2, None, Factory::day13_part2),
+    Solution::new(14, 1, None, Factory::day14_part1),
+    Solution::new(14, 2, None, Factory::day14_part2),
+    Solution::new(15, 1, None, Factory::day15_part1),
+    Solution::new(15, 2, None, Factory::day15_part2),
+    Solution::new(15, 2, Some("Rotated"), Factory::day15_part2_rotated),
+    Solution::new(16, 1, None, Factory::day16_part1),
+    Solution::new(16, 2, None, Factory::day16_part2),
+    Solution::new(17, 1, None, Factory::day17_part1),
+    Solution::new(17, 2, None, Factory::day17_part2),
+    Solution::new(18, 1, None, Factory::day18_part1),
+    Solution::new(18, 2, None, Factory::day18_part2),
+    Solution::new(19, 1, None, Factory::day19_part1),
+    Solution::new(19, 2, None, Factory::day19_part2),
+    Solution::new(20, 1, None, Factory::day20_part1),
+    Solution::new(20, 2, None, Factory::day20_part2),
+    Solution::new(21, 1, None, Factory::day21_part1),
+    Solution::new(21, 2, None, Factory::day21_part2),
+    Solution::new(22, 1, None, Factory::day22_part1),
+    Solution::new(22, 2, None, Factory::day22_part2),
+    Solution::new(23, 1, None, Factory::day23_part1),
+    Solution::new(23, 2, None, Factory::day23_part2),
+    Solution::new(24, 1, None, Factory::day24_part1),
+    Solution::new(24, 2, None, Factory::day24_part2),
+    Solution::new(25, 1, None, Factory::day25_part1),
+];
+
+/// The solutions matching `day`, and `part` and `name` when given. Names
+/// are compared case-insensitively.
+pub fn find(
+    day: Option<u32>,
+    part: Option<u32>,
+    name: Option<&str>,
+) -> impl Iterator<Item = &'static Solution> + '_ {
+    SOLUTIONS.iter().filter(move |s| {
+        day.is_none_or(|d| s.day == d)
+            && part.is_none_or(|p| s.part == p)
+            && name.is_none_or(|n| s.name.is_some_and(|sn| sn.eq_ignore_ascii_case(n)))
+    })
+}
+
+/// The registered days, in order.
+pub fn days() -> impl Iterator<Item = u32> {
+    let mut days: Vec<_> = SOLUTIONS.iter().map(|s| s.day).collect();
+    days.dedup();
+    days.into_iter()
+}
+
+/// Where the bundled puzzle input for `day` lives, in the crate's source
+/// tree so it's found from any working directory.
+pub fn input_path(day: u32) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("input/2022/day{day}.txt"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ordered_and_unique() {
+        assert!(SOLUTIONS
+            .windows(2)
+            .all(|w| (w[0].day, w[0].part, w[0].name) < (w[1].day, w[1].part, w[1].name)));
+    }
+
+    #[test]
+    fn solve_example() {
+        let solution = find(Some(6), Some(1), None).next().unwrap();
+        assert_eq!(solution.label(), "Day 6 - Part 1");
+        assert_eq!(
+            solution.solve("mjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap(),
+            "7"
+        );
+        assert!(solution.solve("abab").is_err());
+    }
+}
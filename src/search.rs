@@ -0,0 +1,354 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+pub type Cost = usize;
+
+/// A directed graph with costed edges, which can be generated as the search
+/// goes rather than built up front.
+pub trait Graph {
+    type Node: Clone + Eq + Hash;
+
+    /// The nodes one step from `node`, with the cost of each step.
+    fn neighbors(&self, node: &Self::Node) -> impl Iterator<Item = (Self::Node, Cost)>;
+}
+
+/// A [`Graph`] whose edges come from a closure, see [`from_fn`].
+pub struct FromFn<N, F> {
+    neighbors: F,
+    node: PhantomData<fn(&N)>,
+}
+
+/// Wraps a closure listing each node's neighbors and step costs as a
+/// [`Graph`].
+pub fn from_fn<N, I, F>(neighbors: F) -> FromFn<N, F>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = (N, Cost)>,
+    F: Fn(&N) -> I,
+{
+    FromFn {
+        neighbors,
+        node: PhantomData,
+    }
+}
+
+impl<N, I, F> Graph for FromFn<N, F>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = (N, Cost)>,
+    F: Fn(&N) -> I,
+{
+    type Node = N;
+
+    fn neighbors(&self, node: &N) -> impl Iterator<Item = (N, Cost)> {
+        (self.neighbors)(node).into_iter()
+    }
+}
+
+/// What a search found: the cheapest known cost of reaching each node it
+/// saw, and the node before each one on the way there.
+///
+/// Once a search stops at a goal, the costs of nodes it hadn't finished
+/// with yet may not be the cheapest; everything up to the goal is exact.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Paths<N: Eq + Hash> {
+    pub dist: HashMap<N, Cost>,
+    pub prev: HashMap<N, N>,
+    /// The goal the search stopped at, if it found one.
+    pub goal: Option<N>,
+}
+
+impl<N: Clone + Eq + Hash> Paths<N> {
+    fn new(starts: impl IntoIterator<Item = N>) -> Self {
+        Self {
+            dist: starts.into_iter().map(|s| (s, 0)).collect(),
+            prev: HashMap::new(),
+            goal: None,
+        }
+    }
+
+    pub fn distance(&self, node: &N) -> Option<Cost> {
+        self.dist.get(node).copied()
+    }
+
+    /// The cost of reaching the goal, if the search found one.
+    pub fn goal_distance(&self) -> Option<Cost> {
+        self.distance(self.goal.as_ref()?)
+    }
+
+    /// The nodes on the way from a start to `node`, including both.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        self.dist.get(node)?;
+
+        let mut path: Vec<_> =
+            std::iter::successors(Some(node.clone()), |n| self.prev.get(n).cloned()).collect();
+        path.reverse();
+        Some(path)
+    }
+
+    /// Records `node` as reached for `cost` via `from`, if that's cheaper
+    /// than any way found so far.
+    fn relax(&mut self, node: &N, cost: Cost, from: &N) -> bool {
+        if self.dist.get(node).is_some_and(|&d| cost >= d) {
+            return false;
+        }
+
+        self.dist.insert(node.clone(), cost);
+        self.prev.insert(node.clone(), from.clone());
+        true
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct State<N> {
+    // cost so far plus the heuristic's estimate of the rest
+    priority: Cost,
+    cost: Cost,
+    node: N,
+}
+
+// The priority queue depends on `Ord`.
+// Explicitly implement the trait so the queue becomes a min-heap
+// instead of a max-heap.
+impl<N: Ord> Ord for State<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Notice that the we flip the ordering on priorities.
+        // In case of a tie we compare the rest - this step is necessary
+        // to make implementations of `PartialEq` and `Ord` consistent.
+        other
+            .priority
+            .cmp(&self.priority)
+            .then_with(|| self.cost.cmp(&other.cost))
+            .then_with(|| self.node.cmp(&other.node))
+    }
+}
+
+// `PartialOrd` needs to be implemented as well.
+impl<N: Ord> PartialOrd for State<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A* from every node in `starts` at once, stopping at the first node
+/// satisfying `is_goal`. `heuristic` must never overestimate the remaining
+/// cost to a goal, nor drop by more than the cost of a step.
+pub fn astar_multi<G>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    mut is_goal: impl FnMut(&G::Node) -> bool,
+    mut heuristic: impl FnMut(&G::Node) -> Cost,
+) -> Paths<G::Node>
+where
+    G: Graph + ?Sized,
+    G::Node: Ord,
+{
+    let starts: Vec<_> = starts.into_iter().collect();
+    let mut paths = Paths::new(starts.iter().cloned());
+    let mut heap: BinaryHeap<_> = starts
+        .into_iter()
+        .map(|node| State {
+            priority: heuristic(&node),
+            cost: 0,
+            node,
+        })
+        .collect();
+
+    // Examine the frontier with the most promising nodes first (min-heap)
+    while let Some(State { cost, node, .. }) = heap.pop() {
+        // Important as we may have already found a better way
+        if paths.distance(&node).is_some_and(|d| cost > d) {
+            continue;
+        }
+
+        if is_goal(&node) {
+            paths.goal = Some(node);
+            break;
+        }
+
+        // For each node we can reach, see if we can find a way with
+        // a lower cost going through this node
+        for (next, step) in graph.neighbors(&node) {
+            let cost = cost + step;
+
+            // If so, add it to the frontier and continue
+            if paths.relax(&next, cost, &node) {
+                heap.push(State {
+                    priority: cost + heuristic(&next),
+                    cost,
+                    node: next,
+                });
+            }
+        }
+    }
+
+    paths
+}
+
+/// A* from `start`, see [`astar_multi`].
+pub fn astar<G>(
+    graph: &G,
+    start: G::Node,
+    is_goal: impl FnMut(&G::Node) -> bool,
+    heuristic: impl FnMut(&G::Node) -> Cost,
+) -> Paths<G::Node>
+where
+    G: Graph + ?Sized,
+    G::Node: Ord,
+{
+    astar_multi(graph, [start], is_goal, heuristic)
+}
+
+/// Dijkstra's algorithm from every node in `starts` at once, stopping at the
+/// first node satisfying `is_goal`. Pass `|_| false` to find the cheapest
+/// way to everything reachable.
+pub fn dijkstra_multi<G>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    is_goal: impl FnMut(&G::Node) -> bool,
+) -> Paths<G::Node>
+where
+    G: Graph + ?Sized,
+    G::Node: Ord,
+{
+    astar_multi(graph, starts, is_goal, |_| 0)
+}
+
+/// Dijkstra's algorithm from `start`, see [`dijkstra_multi`].
+pub fn dijkstra<G>(
+    graph: &G,
+    start: G::Node,
+    is_goal: impl FnMut(&G::Node) -> bool,
+) -> Paths<G::Node>
+where
+    G: Graph + ?Sized,
+    G::Node: Ord,
+{
+    dijkstra_multi(graph, [start], is_goal)
+}
+
+/// Breadth-first search from every node in `starts` at once, counting each
+/// edge as a single step whatever its cost, and stopping at the first node
+/// satisfying `is_goal`.
+pub fn bfs_multi<G>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    mut is_goal: impl FnMut(&G::Node) -> bool,
+) -> Paths<G::Node>
+where
+    G: Graph + ?Sized,
+{
+    let mut queue: VecDeque<_> = starts.into_iter().collect();
+    let mut paths = Paths::new(queue.iter().cloned());
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            paths.goal = Some(node);
+            break;
+        }
+
+        let steps = paths.dist[&node] + 1;
+        for (next, _) in graph.neighbors(&node) {
+            if !paths.dist.contains_key(&next) {
+                paths.dist.insert(next.clone(), steps);
+                paths.prev.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    paths
+}
+
+/// Breadth-first search from `start`, see [`bfs_multi`].
+pub fn bfs<G>(graph: &G, start: G::Node, is_goal: impl FnMut(&G::Node) -> bool) -> Paths<G::Node>
+where
+    G: Graph + ?Sized,
+{
+    bfs_multi(graph, [start], is_goal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //   1     1
+    // 0 --> 1 --> 2
+    //  \         ^
+    //   \---5---/
+    fn weighted() -> impl Graph<Node = u32> {
+        from_fn(|&n: &u32| match n {
+            0 => vec![(1, 1), (2, 5)],
+            1 => vec![(2, 1)],
+            _ => vec![],
+        })
+    }
+
+    // a 10x10 open grid
+    fn grid() -> impl Graph<Node = (i32, i32)> {
+        from_fn(|&(x, y): &(i32, i32)| {
+            [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]
+                .into_iter()
+                .filter(|&(x, y)| (0..10).contains(&x) && (0..10).contains(&y))
+                .map(|p| (p, 1))
+        })
+    }
+
+    #[test]
+    fn dijkstra_weighted() {
+        let graph = weighted();
+
+        let paths = dijkstra(&graph, 0, |&n| n == 2);
+        assert_eq!(paths.goal, Some(2));
+        assert_eq!(paths.goal_distance(), Some(2));
+        assert_eq!(paths.path_to(&2), Some(vec![0, 1, 2]));
+
+        assert_eq!(dijkstra(&graph, 0, |&n| n == 0).goal_distance(), Some(0));
+
+        let paths = dijkstra(&graph, 2, |&n| n == 0);
+        assert_eq!(paths.goal, None);
+        assert_eq!(paths.path_to(&0), None);
+    }
+
+    #[test]
+    fn bfs_counts_steps() {
+        // the direct edge is one step, however much it costs
+        let paths = bfs(&weighted(), 0, |_| false);
+        assert_eq!(paths.distance(&2), Some(1));
+        assert_eq!(paths.path_to(&2), Some(vec![0, 2]));
+    }
+
+    #[test]
+    fn multi_source() {
+        let graph = grid();
+
+        let paths = bfs_multi(&graph, [(0, 0), (9, 9)], |_| false);
+        assert_eq!(paths.distance(&(0, 9)), Some(9));
+        assert_eq!(paths.distance(&(8, 8)), Some(2));
+        assert_eq!(paths.distance(&(4, 4)), Some(8));
+
+        let paths = dijkstra_multi(&graph, [(0, 0), (9, 9)], |&p| p == (7, 9));
+        assert_eq!(paths.goal_distance(), Some(2));
+        assert_eq!(paths.path_to(&(7, 9)).unwrap()[0], (9, 9));
+    }
+
+    #[test]
+    fn astar_matches_dijkstra() {
+        let graph = grid();
+        let goal = (7, 3);
+        let manhattan =
+            |&(x, y): &(i32, i32)| (goal.0 - x).unsigned_abs() + (goal.1 - y).unsigned_abs();
+
+        let found = astar(&graph, (1, 8), |&p| p == goal, |p| manhattan(p) as Cost);
+        let expected = dijkstra(&graph, (1, 8), |&p| p == goal);
+
+        assert_eq!(found.goal_distance(), Some(11));
+        assert_eq!(found.goal_distance(), expected.goal_distance());
+        assert_eq!(found.path_to(&goal).unwrap().len(), 12);
+
+        // the heuristic keeps it from wandering away from the goal
+        assert!(found.dist.len() < expected.dist.len());
+    }
+}